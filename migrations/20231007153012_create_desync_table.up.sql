@@ -0,0 +1,21 @@
+CREATE TYPE desynckind AS ENUM (
+    'unexpected_state',
+    'missing_shot',
+    'unexpected_shot',
+    'touching_ships',
+    'placement_mismatch'
+);
+
+CREATE TABLE desync (
+	desync_id SERIAL NOT NULL,
+	game_id VARCHAR NOT NULL,
+	round_number INTEGER NOT NULL,
+	opponent_board BOOLEAN NOT NULL,
+	kind desynckind NOT NULL,
+	x SMALLINT NOT NULL,
+	y SMALLINT NOT NULL,
+	details VARCHAR NOT NULL,
+	detected_at TIMESTAMP NOT NULL DEFAULT NOW(),
+	CONSTRAINT desync_pk PRIMARY KEY (desync_id),
+	CONSTRAINT desync_game_fk FOREIGN KEY (game_id) REFERENCES game (game_id)
+);
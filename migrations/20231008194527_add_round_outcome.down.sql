@@ -0,0 +1,3 @@
+ALTER TABLE round DROP COLUMN outcome;
+
+DROP TYPE moveoutcome;
@@ -0,0 +1,7 @@
+CREATE TYPE moveoutcome AS ENUM (
+    'miss',
+    'hit',
+    'sink'
+);
+
+ALTER TABLE round ADD COLUMN outcome moveoutcome NULL;
@@ -0,0 +1 @@
+ALTER TABLE game ADD COLUMN opponent_board JSONB NULL;
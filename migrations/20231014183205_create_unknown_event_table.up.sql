@@ -0,0 +1,7 @@
+CREATE TABLE unknown_event (
+	event_id SERIAL NOT NULL,
+	event_type VARCHAR NOT NULL,
+	payload JSONB NOT NULL,
+	received_at TIMESTAMP NOT NULL DEFAULT NOW(),
+	CONSTRAINT unknown_event_pk PRIMARY KEY (event_id)
+);
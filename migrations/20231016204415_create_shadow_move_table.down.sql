@@ -0,0 +1,6 @@
+DROP TABLE shadow_move;
+
+ALTER TABLE round DROP COLUMN heat;
+ALTER TABLE round DROP COLUMN strategy;
+
+DROP TYPE attackstrategy;
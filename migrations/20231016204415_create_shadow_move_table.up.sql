@@ -0,0 +1,21 @@
+CREATE TYPE attackstrategy AS ENUM (
+	'hunt_target',
+	'max_heat',
+	'parity',
+	'random'
+);
+
+ALTER TABLE round ADD COLUMN strategy attackstrategy NULL;
+ALTER TABLE round ADD COLUMN heat INTEGER NULL;
+
+CREATE TABLE shadow_move (
+	shadow_move_id SERIAL NOT NULL,
+	game_id VARCHAR NOT NULL,
+	round_id INTEGER NOT NULL,
+	strategy attackstrategy NOT NULL,
+	move_x SMALLINT NOT NULL,
+	move_y SMALLINT NOT NULL,
+	heat INTEGER NOT NULL,
+	CONSTRAINT shadow_move_pk PRIMARY KEY (shadow_move_id),
+	CONSTRAINT shadow_move_round_fk FOREIGN KEY (game_id, round_id) REFERENCES round (game_id, round_id)
+);
@@ -0,0 +1 @@
+ALTER TABLE game ADD COLUMN variant VARCHAR NULL;
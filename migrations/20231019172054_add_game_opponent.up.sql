@@ -0,0 +1 @@
+ALTER TABLE game ADD COLUMN opponent_id VARCHAR NULL;
@@ -0,0 +1 @@
+ALTER TABLE round DROP COLUMN explanation;
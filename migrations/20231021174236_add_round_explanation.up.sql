@@ -0,0 +1 @@
+ALTER TABLE round ADD COLUMN explanation JSONB NULL;
@@ -0,0 +1,11 @@
+CREATE TABLE event_panic (
+	panic_id SERIAL NOT NULL,
+	game VARCHAR NOT NULL,
+	game_id VARCHAR NULL,
+	event_type VARCHAR NULL,
+	round INTEGER NULL,
+	message VARCHAR NOT NULL,
+	payload JSONB NOT NULL,
+	occurred_at TIMESTAMP NOT NULL DEFAULT NOW(),
+	CONSTRAINT event_panic_pk PRIMARY KEY (panic_id)
+);
@@ -0,0 +1,8 @@
+ALTER TABLE round DROP COLUMN error;
+
+CREATE SEQUENCE round_round_id_seq OWNED BY round.round_id;
+SELECT setval('round_round_id_seq', GREATEST(COALESCE((SELECT MAX(round_id) FROM round), 0), 0) + 1, false);
+ALTER TABLE round ALTER COLUMN round_id SET DEFAULT nextval('round_round_id_seq');
+
+ALTER TABLE shadow_move DROP CONSTRAINT shadow_move_round_fk;
+ALTER TABLE shadow_move ADD CONSTRAINT shadow_move_round_fk FOREIGN KEY (game_id, round_id) REFERENCES round (game_id, round_id);
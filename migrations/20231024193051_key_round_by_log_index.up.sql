@@ -0,0 +1,20 @@
+-- Rounds are keyed by their index in the game log instead of a serial, so
+-- redelivered events update their rows instead of adding new ones
+ALTER TABLE shadow_move DROP CONSTRAINT shadow_move_round_fk;
+ALTER TABLE shadow_move ADD CONSTRAINT shadow_move_round_fk FOREIGN KEY (game_id, round_id) REFERENCES round (game_id, round_id) ON UPDATE CASCADE;
+
+ALTER TABLE round ALTER COLUMN round_id DROP DEFAULT;
+DROP SEQUENCE round_round_id_seq;
+
+-- Rounds stored before can't be mapped to log indices: opponent moves were
+-- stored in reverse, our attacks before the server logged them and the final
+-- moves not at all. They get negative ids in insertion order instead, so they
+-- stay apart from log indices and still sort before them.
+UPDATE round SET round_id = -numbered.position
+FROM (
+	SELECT game_id, round_id, ROW_NUMBER() OVER (PARTITION BY game_id ORDER BY round_id DESC) AS position
+	FROM round
+) AS numbered
+WHERE round.game_id = numbered.game_id AND round.round_id = numbered.round_id;
+
+ALTER TABLE round ADD COLUMN error VARCHAR NULL;
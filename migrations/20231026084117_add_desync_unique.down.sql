@@ -0,0 +1 @@
+ALTER TABLE desync DROP CONSTRAINT desync_uq;
@@ -0,0 +1,8 @@
+-- A desync stays on the board, so it is only recorded in the round it first
+-- showed up in
+DELETE FROM desync
+WHERE desync_id NOT IN (
+	SELECT MIN(desync_id) FROM desync GROUP BY game_id, opponent_board, kind, x, y
+);
+
+ALTER TABLE desync ADD CONSTRAINT desync_uq UNIQUE (game_id, opponent_board, kind, x, y);
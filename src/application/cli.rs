@@ -0,0 +1,63 @@
+use std::path::PathBuf;
+
+pub enum Command {
+    /// Connect to the game server and play
+    Run,
+    /// Explain our attack in a round of a stored game
+    Explain { game_id: String, round: usize },
+    /// Export images of a stored game
+    Export { game_id: String, directory: PathBuf },
+    /// Run recorded traffic through the game controller
+    Replay {
+        paths: Vec<PathBuf>,
+        variant: Option<String>,
+    },
+    /// Compare shadow strategies with the moves we played
+    ShadowReport,
+    /// Compare the win rates of strategy variants
+    VariantReport,
+}
+
+const USAGE: &str = "Usage:
+    battleship                              Play on the game server
+    battleship explain <game id> <round>    Explain our attack in a round of a stored game
+    battleship export <game id> [directory] Export images of a stored game
+    battleship replay [--variant <name>] <path>...
+                                            Replay recorded traffic files or directories
+    battleship shadows                      Compare shadow strategies with played moves
+    battleship variants                     Compare the win rates of strategy variants";
+
+pub fn parse_command() -> Command {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    let args = args.iter().map(String::as_str).collect::<Vec<_>>();
+
+    match args.as_slice() {
+        [] => Command::Run,
+        ["explain", game_id, round] if round.parse::<usize>().is_ok() => Command::Explain {
+            game_id: game_id.to_string(),
+            round: round.parse().unwrap(),
+        },
+        ["export", game_id] => Command::Export {
+            game_id: game_id.to_string(),
+            directory: PathBuf::from("exports").join(game_id),
+        },
+        ["export", game_id, directory] => Command::Export {
+            game_id: game_id.to_string(),
+            directory: PathBuf::from(directory),
+        },
+        ["replay", "--variant", variant, paths @ ..] if !paths.is_empty() => Command::Replay {
+            paths: paths.iter().map(PathBuf::from).collect(),
+            variant: Some(variant.to_string()),
+        },
+        ["replay", paths @ ..] if !paths.is_empty() => Command::Replay {
+            paths: paths.iter().map(PathBuf::from).collect(),
+            variant: None,
+        },
+        ["shadows"] => Command::ShadowReport,
+        ["variants"] => Command::VariantReport,
+        _ => {
+            eprintln!("{}", USAGE);
+            std::process::exit(2);
+        }
+    }
+}
@@ -0,0 +1,226 @@
+use std::{
+    collections::HashMap,
+    io::Write,
+    sync::{Mutex, PoisonError},
+    time::{Duration, Instant},
+};
+
+use itertools::Itertools;
+use lazy_static::lazy_static;
+
+use crate::{
+    bot::boundary::get_panic_count,
+    game::{
+        guard::get_intervention_count,
+        logic::{heatmap::Heatmap, render::BoardRenderer},
+        session::get_session,
+    },
+    network::models::{Board, GameEvent, GameResponse, Position},
+};
+
+lazy_static! {
+    static ref ENABLED: bool = std::env::var("DASHBOARD")
+        .map(|value| value == "true")
+        .unwrap_or(false);
+    static ref DASHBOARD: Mutex<Dashboard> = Mutex::new(Dashboard::default());
+}
+
+const REFRESH_INTERVAL: Duration = Duration::from_millis(500);
+/// Only the most recently updated games get their boards drawn
+const MAX_VISIBLE_GAMES: usize = 4;
+
+#[derive(Default)]
+struct Dashboard {
+    games: HashMap<String, GameView>,
+    wins: u32,
+    losses: u32,
+    ties: u32,
+}
+
+struct GameView {
+    opponent: String,
+    rounds: usize,
+    self_board: Option<Board>,
+    opponent_board: Option<Board>,
+    heatmap: Option<Heatmap>,
+    last_move: Option<Position>,
+    latency: Option<Duration>,
+    updated_at: Instant,
+}
+
+pub fn is_dashboard_enabled() -> bool {
+    *ENABLED
+}
+
+/// Redraws the dashboard periodically, logs are only written to files while
+/// it is running.
+pub fn start_dashboard() {
+    tokio::spawn(async {
+        let mut interval = tokio::time::interval(REFRESH_INTERVAL);
+
+        loop {
+            interval.tick().await;
+
+            let frame = DASHBOARD
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .render();
+            let mut stdout = std::io::stdout().lock();
+
+            // Clear screen and move cursor to the top left
+            write!(stdout, "\x1b[2J\x1b[H{}", frame).ok();
+            stdout.flush().ok();
+        }
+    });
+}
+
+/// Updates the dashboard with an incoming event, before it is handled.
+pub fn record_event(event: &GameEvent) {
+    let details = match event.get_details() {
+        Some(details) => details,
+        None => return,
+    };
+
+    // Resolved before locking, a malformed event must not poison the dashboard
+    let (self_player, opponent) = match (details.find_self(), details.find_opponent()) {
+        (Some(self_player), Some(opponent)) => (self_player, opponent),
+        _ => return,
+    };
+
+    let boards = match event {
+        GameEvent::Round(event) => details
+            .find_self_number()
+            .zip(details.find_opponent_number())
+            .and_then(|(self_number, opponent_number)| {
+                Some((
+                    event.boards.get(self_number)?.clone(),
+                    event.boards.get(opponent_number)?.clone(),
+                ))
+            }),
+        _ => None,
+    };
+
+    let mut dashboard = DASHBOARD.lock().unwrap_or_else(PoisonError::into_inner);
+
+    if let GameEvent::Result(event) = event {
+        if self_player.score > opponent.score {
+            dashboard.wins += 1;
+        } else if self_player.score < opponent.score {
+            dashboard.losses += 1;
+        } else {
+            dashboard.ties += 1;
+        }
+
+        dashboard.games.remove(&event.details.id);
+        return;
+    }
+
+    let view = dashboard
+        .games
+        .entry(details.id.clone())
+        .or_insert_with(|| GameView {
+            opponent: opponent.id.clone(),
+            rounds: 0,
+            self_board: None,
+            opponent_board: None,
+            heatmap: None,
+            last_move: None,
+            latency: None,
+            updated_at: Instant::now(),
+        });
+
+    view.rounds = details.log.len();
+    view.updated_at = Instant::now();
+
+    if let Some((self_board, opponent_board)) = boards {
+        view.self_board = Some(self_board);
+        view.opponent_board = Some(opponent_board);
+    }
+}
+
+/// Updates the dashboard with our response and the analysis of the session.
+pub async fn record_response(game_id: &str, response: Option<&GameResponse>, latency: Duration) {
+    let heatmap = match get_session(game_id) {
+        Some(session) => session.lock().await.heatmap.clone(),
+        None => None,
+    };
+
+    let mut dashboard = DASHBOARD.lock().unwrap_or_else(PoisonError::into_inner);
+
+    if let Some(view) = dashboard.games.get_mut(game_id) {
+        view.latency = Some(latency);
+        view.heatmap = heatmap;
+
+        if let Some(GameResponse::Attack(position)) = response {
+            view.last_move = Some(*position);
+        }
+    }
+}
+
+impl Dashboard {
+    fn render(&self) -> String {
+        let mut lines = vec![format!(
+            "Active games: {} | Wins: {} | Losses: {} | Ties: {} | Interventions: {} | Panics: {}",
+            self.games.len(),
+            self.wins,
+            self.losses,
+            self.ties,
+            get_intervention_count(),
+            get_panic_count()
+        )];
+
+        let games = self
+            .games
+            .iter()
+            .sorted_by_key(|(_, view)| std::cmp::Reverse(view.updated_at))
+            .collect_vec();
+
+        for (game_id, view) in games.iter().take(MAX_VISIBLE_GAMES) {
+            lines.push(String::new());
+            lines.push(format!(
+                "Game {} vs {} | Round {} | Last move {} | Latency {}",
+                game_id,
+                view.opponent,
+                view.rounds,
+                view.last_move
+                    .map(|position| position.to_string())
+                    .unwrap_or(String::from("-")),
+                view.latency
+                    .map(|latency| format!("{:?}", latency))
+                    .unwrap_or(String::from("-")),
+            ));
+
+            let mut renderer = BoardRenderer::new().colored(true);
+
+            if let Some(board) = &view.self_board {
+                renderer = renderer.self_board(board);
+            }
+
+            if let Some(board) = &view.opponent_board {
+                renderer = renderer.opponent_board(board);
+            }
+
+            if let Some(heatmap) = &view.heatmap {
+                renderer = renderer.heatmap(heatmap);
+            }
+
+            if let Some(position) = view.last_move {
+                renderer = renderer.shot(position);
+            }
+
+            if view.self_board.is_some() || view.opponent_board.is_some() {
+                lines.push(renderer.render());
+            }
+        }
+
+        if games.len() > MAX_VISIBLE_GAMES {
+            lines.push(String::new());
+            lines.push(format!(
+                "... and {} more games",
+                games.len() - MAX_VISIBLE_GAMES
+            ));
+        }
+
+        lines.join("\n")
+    }
+}
@@ -1,6 +1,7 @@
 use sqlx::{postgres::PgPoolOptions, PgPool};
 use tokio::sync::OnceCell;
 
+/// Only unset when replaying traffic, handlers skip persistence in that case
 pub static DB_POOL: OnceCell<PgPool> = OnceCell::const_new();
 
 pub async fn init_database_connection() {
@@ -1,4 +1,6 @@
 use anyhow::Result;
+
+use super::dashboard::is_dashboard_enabled;
 use flexi_logger::{
     detailed_format, Age, Cleanup, Criterion, Duplicate, FileSpec, Logger, LoggerHandle, Naming,
     WriteMode,
@@ -17,10 +19,18 @@ pub fn try_start_logger() -> Result<LoggerHandle> {
     Trace
     */
     let level = std::env::var("LOGLEVEL").unwrap_or(String::from("Info"));
+
+    // The dashboard takes over the terminal
+    let duplicate = if is_dashboard_enabled() {
+        Duplicate::None
+    } else {
+        Duplicate::Info
+    };
+
     let logger_handle = Logger::try_with_str(level)?
         .log_to_file(FileSpec::default().directory("logs"))
         .write_mode(WriteMode::Direct)
-        .duplicate_to_stdout(Duplicate::Info)
+        .duplicate_to_stdout(duplicate)
         .format_for_files(detailed_format)
         .rotate(
             Criterion::AgeOrSize(Age::Day, 1024 * 1024 * 25),
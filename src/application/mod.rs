@@ -1,2 +1,5 @@
+pub mod cli;
+pub mod dashboard;
 pub mod database;
 pub mod logging;
+pub mod thread_pool;
@@ -0,0 +1,62 @@
+use rayon::{ThreadPool, ThreadPoolBuilder};
+use tokio::sync::{OnceCell, Semaphore};
+
+pub static THREAD_POOL: OnceCell<ThreadPool> = OnceCell::const_new();
+/// Limits the compute tasks in flight, the others wait without holding a
+/// blocking thread
+static COMPUTE_PERMITS: OnceCell<Semaphore> = OnceCell::const_new();
+
+pub fn init_thread_pool() {
+    // 0 lets rayon pick the number of available cores
+    let threads = std::env::var("COMPUTE_THREADS")
+        .ok()
+        .and_then(|threads| threads.parse::<usize>().ok())
+        .unwrap_or(0);
+
+    let pool = ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .thread_name(|index| format!("compute-{}", index))
+        .build()
+        .expect("Failed to create thread pool");
+
+    // Defaults to one task per compute thread
+    let permits = std::env::var("MAX_COMPUTE_TASKS")
+        .ok()
+        .and_then(|permits| permits.parse::<usize>().ok())
+        .filter(|&permits| permits > 0)
+        .unwrap_or(pool.current_num_threads());
+
+    COMPUTE_PERMITS
+        .set(Semaphore::new(permits))
+        .expect("Failed to set compute permits");
+    THREAD_POOL.set(pool).expect("Failed to set thread pool");
+}
+
+/// Runs a CPU-heavy task on the compute pool without blocking the async
+/// runtime, so concurrent games don't starve the socket event loop. Waits
+/// while `MAX_COMPUTE_TASKS` tasks are running.
+pub async fn spawn_compute<F, R>(task: F) -> R
+where
+    F: FnOnce() -> R + Send + 'static,
+    R: Send + 'static,
+{
+    let _permit = COMPUTE_PERMITS
+        .get()
+        .expect("Thread pool not initialized")
+        .acquire()
+        .await
+        .expect("Compute permits closed");
+
+    tokio::task::spawn_blocking(move || {
+        THREAD_POOL
+            .get()
+            .expect("Thread pool not initialized")
+            .install(task)
+    })
+    .await
+    .unwrap_or_else(|err| match err.try_into_panic() {
+        // Keeps the original message for the panic boundary of the event
+        Ok(panic) => std::panic::resume_unwind(panic),
+        Err(err) => panic!("Compute task failed: {}", err),
+    })
+}
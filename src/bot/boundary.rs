@@ -0,0 +1,96 @@
+use std::{
+    any::Any,
+    panic::AssertUnwindSafe,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use futures_util::FutureExt;
+use log::error;
+use serde_json::Value;
+use sqlx::{query, types::Json};
+
+use crate::application::database::DB_POOL;
+
+use super::GameBot;
+
+/// Number of events that panicked since the start
+static PANICS: AtomicUsize = AtomicUsize::new(0);
+
+pub fn get_panic_count() -> usize {
+    PANICS.load(Ordering::Relaxed)
+}
+
+/// Handles an event like [`GameBot::handle_event`], but catches panics. A
+/// panic is logged and recorded with its game and answered with the bot's
+/// fallback response, so the server still gets an ack and other games keep
+/// running.
+pub async fn handle_event_guarded(
+    bot: &dyn GameBot,
+    event: Value,
+) -> anyhow::Result<Option<Value>> {
+    let panic = match AssertUnwindSafe(bot.handle_event(event.clone()))
+        .catch_unwind()
+        .await
+    {
+        Ok(response) => return response,
+        Err(panic) => panic,
+    };
+
+    PANICS.fetch_add(1, Ordering::Relaxed);
+
+    let message = get_panic_message(panic.as_ref());
+    let game_id = event.get("id").and_then(Value::as_str);
+    let event_type = event.get("type").and_then(Value::as_str);
+    let round = event.get("log").and_then(Value::as_array).map(Vec::len);
+
+    error!(
+        "Panic in {} {} event of game {} in round {}: {}",
+        bot.get_name(),
+        event_type.unwrap_or("-"),
+        game_id.unwrap_or("-"),
+        round.unwrap_or(0),
+        message
+    );
+
+    if let Some(pool) = DB_POOL.get() {
+        let result = query!(
+            "INSERT INTO event_panic (game, game_id, event_type, round, message, payload) VALUES ($1, $2, $3, $4, $5, $6)",
+            bot.get_name(),
+            game_id,
+            event_type,
+            round.map(|round| round as i32),
+            message,
+            Json(&event) as _,
+        )
+        .execute(pool)
+        .await;
+
+        if let Err(err) = result {
+            error!("Failed to insert panic into database: {}", err);
+        }
+    }
+
+    let fallback = std::panic::catch_unwind(AssertUnwindSafe(|| bot.get_fallback_response(&event)));
+
+    match fallback {
+        Ok(fallback) => Ok(fallback),
+        Err(_) => {
+            error!(
+                "Fallback response panicked too in game {}, not acking",
+                game_id.unwrap_or("-")
+            );
+            Ok(None)
+        }
+    }
+}
+
+fn get_panic_message(panic: &(dyn Any + Send)) -> String {
+    if let Some(message) = panic.downcast_ref::<&str>() {
+        return message.to_string();
+    }
+
+    match panic.downcast_ref::<String>() {
+        Some(message) => message.clone(),
+        None => String::from("unknown panic"),
+    }
+}
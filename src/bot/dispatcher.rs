@@ -0,0 +1,130 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use anyhow::anyhow;
+use lazy_static::lazy_static;
+use log::{debug, warn};
+use serde_json::Value;
+use tokio::sync::{mpsc, oneshot};
+
+use super::{boundary::handle_event_guarded, GameBot};
+
+/// Games without events for this long lose their task and what the bot keeps
+/// for them, in case their `RESULT` never arrives
+pub const IDLE_TIMEOUT: Duration = Duration::from_secs(600);
+
+type Response = anyhow::Result<Option<Value>>;
+
+struct Envelope {
+    event: Value,
+    respond_to: oneshot::Sender<Response>,
+}
+
+lazy_static! {
+    static ref MAILBOXES: Mutex<HashMap<String, mpsc::UnboundedSender<Envelope>>> =
+        Mutex::new(HashMap::new());
+}
+
+/// Routes an event to the task of its game and returns where its response
+/// will arrive. Each game has one task that handles its events in the order
+/// they were dispatched, different games run concurrently. Events without a
+/// game id get a task of their own.
+pub fn dispatch(bot: Arc<dyn GameBot>, event: Value) -> oneshot::Receiver<Response> {
+    let (respond_to, response) = oneshot::channel();
+
+    let game_id = match event.get("id").and_then(Value::as_str) {
+        Some(game_id) => game_id.to_string(),
+        None => {
+            tokio::spawn(async move {
+                respond_to
+                    .send(handle_event_guarded(bot.as_ref(), event).await)
+                    .ok();
+            });
+
+            return response;
+        }
+    };
+
+    let mut mailboxes = MAILBOXES.lock().unwrap();
+    let mailbox = mailboxes
+        .entry(game_id.clone())
+        .or_insert_with(|| spawn_game_task(bot, game_id.clone()));
+
+    // Tasks only stop after removing their mailbox, so this only fails if the
+    // runtime is shutting down
+    if let Err(mpsc::error::SendError(envelope)) = mailbox.send(Envelope { event, respond_to }) {
+        envelope
+            .respond_to
+            .send(Err(anyhow!("Task of game {} stopped", game_id)))
+            .ok();
+    }
+
+    response
+}
+
+fn spawn_game_task(bot: Arc<dyn GameBot>, game_id: String) -> mpsc::UnboundedSender<Envelope> {
+    let (sender, mut mailbox) = mpsc::unbounded_channel::<Envelope>();
+
+    tokio::spawn(async move {
+        debug!("Started task of game {}", game_id);
+
+        let mut stopping = false;
+
+        loop {
+            let envelope = if stopping {
+                // Decided under the lock, so no event can be dispatched to the
+                // mailbox after it was found empty
+                let mut mailboxes = MAILBOXES.lock().unwrap();
+
+                match mailbox.try_recv() {
+                    Ok(envelope) => envelope,
+                    Err(_) => {
+                        mailboxes.remove(&game_id);
+                        break;
+                    }
+                }
+            } else {
+                match tokio::time::timeout(IDLE_TIMEOUT, mailbox.recv()).await {
+                    Ok(Some(envelope)) => envelope,
+                    Ok(None) => break,
+                    Err(_) => {
+                        warn!("Game {} is idle, stopping its task", game_id);
+
+                        let mut mailboxes = MAILBOXES.lock().unwrap();
+
+                        match mailbox.try_recv() {
+                            Ok(envelope) => envelope,
+                            Err(_) => {
+                                bot.drop_game(&game_id);
+                                mailboxes.remove(&game_id);
+                                break;
+                            }
+                        }
+                    }
+                }
+            };
+
+            let is_result = envelope.event.get("type").and_then(Value::as_str) == Some("RESULT");
+
+            handle_envelope(bot.as_ref(), envelope).await;
+
+            // Events dispatched after the result still get handled in order,
+            // the task stops once its mailbox is empty
+            stopping |= is_result;
+        }
+
+        debug!("Stopped task of game {}", game_id);
+    });
+
+    sender
+}
+
+async fn handle_envelope(bot: &dyn GameBot, envelope: Envelope) {
+    let response = handle_event_guarded(bot, envelope.event).await;
+
+    // The receiver is gone if the connection dropped the callback
+    envelope.respond_to.send(response).ok();
+}
@@ -0,0 +1,40 @@
+use std::sync::Arc;
+
+use anyhow::bail;
+use futures_util::future::BoxFuture;
+use serde_json::Value;
+
+use crate::{game::bot::BattleshipBot, tictactoe::bot::TicTacToeBot};
+
+pub mod boundary;
+pub mod dispatcher;
+
+/// A game the bot can play. The socket layer only passes raw events to it and
+/// acknowledges them with the returned response, if there is one.
+pub trait GameBot: Send + Sync {
+    fn get_name(&self) -> &'static str;
+
+    fn handle_event(&self, event: Value) -> BoxFuture<'static, anyhow::Result<Option<Value>>>;
+
+    /// Response to send if handling the event panicked, `None` to not ack.
+    /// Must not depend on any state the panic could have left behind.
+    fn get_fallback_response(&self, _event: &Value) -> Option<Value> {
+        None
+    }
+
+    /// Called when a game stopped sending events before its `RESULT`, to free
+    /// what the bot keeps for it.
+    fn drop_game(&self, _game_id: &str) {}
+}
+
+/// Picks the game to play on this connection from `GAME`, defaulting to
+/// battleship.
+pub fn get_bot() -> anyhow::Result<Arc<dyn GameBot>> {
+    let game = std::env::var("GAME").unwrap_or_else(|_| String::from("battleship"));
+
+    match game.as_str() {
+        "battleship" => Ok(Arc::new(BattleshipBot)),
+        "tictactoe" => Ok(Arc::new(TicTacToeBot)),
+        _ => bail!("Unknown GAME {}", game),
+    }
+}
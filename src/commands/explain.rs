@@ -0,0 +1,88 @@
+use anyhow::{anyhow, bail, Result};
+use log::info;
+use sqlx::{query, types::Json};
+
+use crate::{
+    application::database::DB_POOL,
+    game::logic::{board_diff::MoveOutcome, explanation::MoveExplanation},
+    network::models::Position,
+};
+
+/// Prints why we chose our attack in a round of a stored game. Rounds are
+/// indices into the game log, the same as the stored round ids and desync
+/// round numbers. Rounds stored before they were keyed by log index have
+/// negative ids and can't be explained.
+pub async fn explain_move(game_id: &str, round: usize) -> Result<()> {
+    let pool = DB_POOL.get().unwrap();
+
+    let row = query!(
+        r#"SELECT opponent_move, move_x, move_y, outcome as "outcome: MoveOutcome", explanation as "explanation: Json<MoveExplanation>"
+        FROM round WHERE game_id = $1 AND round_id = $2"#,
+        game_id,
+        i32::try_from(round)?
+    )
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| anyhow!("Round {} of game {} not found", round, game_id))?;
+
+    if row.opponent_move {
+        bail!(
+            "Round {} of game {} is a move of the opponent",
+            round,
+            game_id
+        );
+    }
+
+    let position = match (row.move_x, row.move_y) {
+        (Some(x), Some(y)) => Position {
+            x: x as i8,
+            y: y as i8,
+        }
+        .to_string(),
+        _ => String::from("nothing"),
+    };
+
+    let outcome = match row.outcome {
+        Some(outcome) => format!("{:?}", outcome),
+        None => String::from("unknown"),
+    };
+
+    info!(
+        "Game {} round {}: attacked {}, outcome {}",
+        game_id, round, position, outcome
+    );
+
+    let explanation = match row.explanation {
+        Some(Json(explanation)) => explanation,
+        None => {
+            info!("No explanation stored, the move was sent by the guard or predates explanations");
+            return Ok(());
+        }
+    };
+
+    info!("Strategy: {}", explanation.strategy);
+
+    if let Some(stage) = explanation.stage {
+        info!("Stage: {}", stage.get_name());
+    }
+
+    info!(
+        "Tie-break: {}, {} of {} candidates tied",
+        explanation.tie_break.get_name(),
+        explanation.ties,
+        explanation.candidate_count
+    );
+    info!("Time spent: {}ms", explanation.duration_ms);
+
+    for candidate in &explanation.candidates {
+        let marker = if explanation.position == Some(candidate.position) {
+            " <- chosen"
+        } else {
+            ""
+        };
+
+        info!("  {}{}", candidate, marker);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,74 @@
+use std::{fs, path::Path};
+
+use anyhow::{anyhow, Result};
+use log::info;
+use sqlx::{query, types::Json};
+
+use crate::{
+    application::database::DB_POOL,
+    game::logic::{board_diff::MoveOutcome, export::BoardImage, heatmap::Heatmap},
+    network::models::{Board, Position},
+};
+
+/// Writes an SVG and PNG image of the opponent board before each of our
+/// moves in a stored game, followed by the final board.
+pub async fn export_game(game_id: &str, directory: &Path) -> Result<()> {
+    let pool = DB_POOL.get().unwrap();
+
+    let game = query!(
+        r#"SELECT opponent_board as "opponent_board: Json<Board>" FROM game WHERE game_id = $1"#,
+        game_id
+    )
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| anyhow!("Game {} not found", game_id))?;
+
+    let rounds = query!(
+        r#"SELECT move_x, move_y, outcome as "outcome: MoveOutcome" FROM round WHERE game_id = $1 AND opponent_move = false ORDER BY round_id"#,
+        game_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    fs::create_dir_all(directory)?;
+
+    let mut board = Board::new();
+
+    for (index, round) in rounds.into_iter().enumerate() {
+        let position = match (round.move_x, round.move_y) {
+            (Some(x), Some(y)) => Position {
+                x: x as i8,
+                y: y as i8,
+            },
+            _ => continue,
+        };
+
+        let heatmap = Heatmap::compute(board.clone()).await;
+        let image = BoardImage::new(&board).heatmap(&heatmap).shot(position);
+
+        write_image(directory, &format!("round_{:03}", index + 1), &image)?;
+
+        if let Some(outcome) = round.outcome {
+            board.apply_shot(position, outcome);
+        }
+    }
+
+    let mut image = BoardImage::new(&board);
+
+    if let Some(Json(opponent_board)) = &game.opponent_board {
+        image = image.revealed_board(opponent_board);
+    }
+
+    write_image(directory, "final", &image)?;
+
+    info!("Exported game {} to {}", game_id, directory.display());
+
+    Ok(())
+}
+
+fn write_image(directory: &Path, name: &str, image: &BoardImage) -> Result<()> {
+    fs::write(directory.join(format!("{}.svg", name)), image.to_svg())?;
+    fs::write(directory.join(format!("{}.png", name)), image.to_png()?)?;
+
+    Ok(())
+}
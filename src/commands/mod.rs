@@ -0,0 +1,11 @@
+mod explain;
+mod export;
+mod replay;
+mod shadow_report;
+mod variant_report;
+
+pub use explain::*;
+pub use export::*;
+pub use replay::*;
+pub use shadow_report::*;
+pub use variant_report::*;
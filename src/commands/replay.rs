@@ -0,0 +1,234 @@
+use std::{collections::HashMap, fs, path::PathBuf};
+
+use anyhow::{anyhow, Result};
+use log::{info, warn};
+use serde_json::Value;
+
+use crate::{
+    game::{
+        controller::handle_game_event,
+        session::{get_session, insert_session, GameSession},
+        variant::{find_variant, Variant},
+    },
+    network::{
+        models::{GameEvent, GameResponse, Position},
+        recorder::{TrafficDirection, TrafficRecord},
+    },
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ResponseKind {
+    Attack,
+    Placement,
+    None,
+}
+
+struct Divergence {
+    game_id: String,
+    round: usize,
+    kind: ResponseKind,
+    recorded: Option<Value>,
+    replayed: Option<Value>,
+}
+
+#[derive(Default)]
+struct ReplayReport {
+    events: usize,
+    invalid_events: usize,
+    attacks: usize,
+    placements: usize,
+    invalid_placements: usize,
+    divergences: Vec<Divergence>,
+}
+
+/// Runs recorded traffic through the game controller and compares our new
+/// attacks with the recorded ones. Nothing is written to the database.
+///
+/// Recordings don't contain the variant of their games, so every game is
+/// replayed with the named variant, or the default one. Placements are random
+/// and only checked for being legal.
+pub async fn replay_traffic(paths: &[PathBuf], variant: Option<&str>) -> Result<()> {
+    let variant = match variant {
+        Some(name) => find_variant(name).ok_or_else(|| anyhow!("Unknown variant {}", name))?,
+        None => Variant::default(),
+    };
+
+    info!("Replaying with variant {}", variant.name);
+
+    let mut records = Vec::new();
+
+    for path in get_recording_files(paths)? {
+        for line in fs::read_to_string(&path)?.lines() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            records.push(serde_json::from_str::<TrafficRecord>(line)?);
+        }
+    }
+
+    let mut report = ReplayReport::default();
+
+    for (record, recorded) in pair_responses(records) {
+        report.events += 1;
+
+        let event = match serde_json::from_value::<GameEvent>(record.payload) {
+            Ok(event) => event,
+            Err(err) => {
+                warn!("Skipping invalid event: {}", err);
+                report.invalid_events += 1;
+                continue;
+            }
+        };
+
+        let (game_id, round) = match event.get_details() {
+            Some(details) => (details.id.clone(), details.log.len()),
+            None => (String::from("-"), 0),
+        };
+        let kind = match event {
+            GameEvent::Round(_) => ResponseKind::Attack,
+            GameEvent::Set(_) => ResponseKind::Placement,
+            _ => ResponseKind::None,
+        };
+
+        if event.get_details().is_some() && get_session(&game_id).is_none() {
+            insert_session(GameSession::new(&game_id, variant.clone()));
+        }
+
+        let response = handle_game_event(event).await;
+
+        if let Some(GameResponse::PlaceShips(placement)) = &response {
+            let errors = placement.validate();
+
+            if !errors.is_empty() {
+                warn!("Invalid placement in game {}: {:?}", game_id, errors);
+                report.invalid_placements += 1;
+            }
+        }
+
+        let replayed = response.map(|response| serde_json::to_value(response).unwrap());
+
+        match kind {
+            ResponseKind::Attack => report.attacks += 1,
+            ResponseKind::Placement => report.placements += 1,
+            ResponseKind::None => {}
+        }
+
+        // Placements are random, comparing them tells nothing
+        if kind != ResponseKind::Placement && replayed != recorded {
+            report.divergences.push(Divergence {
+                game_id,
+                round,
+                kind,
+                recorded,
+                replayed,
+            });
+        }
+    }
+
+    report.print();
+
+    Ok(())
+}
+
+/// Expands directories to the recording files they contain, in order.
+fn get_recording_files(paths: &[PathBuf]) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+
+    for path in paths {
+        if !path.is_dir() {
+            files.push(path.clone());
+            continue;
+        }
+
+        let mut directory_files = fs::read_dir(path)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .filter(|file| {
+                file.extension()
+                    .is_some_and(|extension| extension == "ndjson")
+            })
+            .collect::<Vec<_>>();
+
+        directory_files.sort();
+        files.extend(directory_files);
+    }
+
+    Ok(files)
+}
+
+/// Matches every incoming event with the response sent for its packet id.
+/// Packet ids restart on reconnect, so only the next response counts.
+fn pair_responses(records: Vec<TrafficRecord>) -> Vec<(TrafficRecord, Option<Value>)> {
+    let mut events: Vec<(TrafficRecord, Option<Value>)> = Vec::new();
+    let mut pending: HashMap<i32, usize> = HashMap::new();
+
+    for record in records {
+        match record.direction {
+            TrafficDirection::Incoming => {
+                if let Some(packet_id) = record.packet_id {
+                    pending.insert(packet_id, events.len());
+                }
+
+                events.push((record, None));
+            }
+            TrafficDirection::Outgoing => {
+                let index = record
+                    .packet_id
+                    .and_then(|packet_id| pending.remove(&packet_id));
+
+                if let Some(index) = index {
+                    events[index].1 = Some(record.payload);
+                }
+            }
+        }
+    }
+
+    events
+}
+
+impl ReplayReport {
+    fn print(&self) {
+        let count = |kind: ResponseKind| {
+            self.divergences
+                .iter()
+                .filter(|divergence| divergence.kind == kind)
+                .count()
+        };
+
+        info!(
+            "Replayed {} events, {} invalid",
+            self.events, self.invalid_events
+        );
+        info!(
+            "Attacks: {} replayed, {} diverged",
+            self.attacks,
+            count(ResponseKind::Attack)
+        );
+        info!(
+            "Placements: {} replayed, {} invalid",
+            self.placements, self.invalid_placements
+        );
+
+        for divergence in &self.divergences {
+            info!(
+                "Game {} round {}: recorded {}, replayed {}",
+                divergence.game_id,
+                divergence.round,
+                format_response(&divergence.recorded),
+                format_response(&divergence.replayed)
+            );
+        }
+    }
+}
+
+fn format_response(response: &Option<Value>) -> String {
+    match response {
+        Some(value) => match serde_json::from_value::<Position>(value.clone()) {
+            Ok(position) => position.to_string(),
+            Err(_) => value.to_string(),
+        },
+        None => String::from("nothing"),
+    }
+}
@@ -0,0 +1,125 @@
+use std::collections::{HashMap, HashSet};
+
+use anyhow::Result;
+use itertools::Itertools;
+use log::info;
+use sqlx::{query, types::Json};
+
+use crate::{
+    application::database::DB_POOL,
+    game::logic::{board_diff::MoveOutcome, strategy::AttackStrategy},
+    network::models::{Board, FieldState, Position},
+};
+
+#[derive(Default)]
+struct ShadowStats {
+    rounds: usize,
+    hits: usize,
+    misses: usize,
+    unrevealed: usize,
+    played_hits: usize,
+    played_misses: usize,
+}
+
+/// Compares how often each shadow strategy would have hit with the moves we
+/// played in the same rounds. Hits are looked up on the final opponent board.
+/// Boards showing the remaining ships reveal every ship, so their unknown
+/// fields are misses. Moves on boards without any ship are counted separately.
+pub async fn report_shadow_strategies() -> Result<()> {
+    let pool = DB_POOL.get().unwrap();
+
+    let boards = query!(
+        r#"SELECT game_id, opponent_board as "opponent_board!: Json<Board>" FROM game WHERE opponent_board IS NOT NULL"#
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|game| (game.game_id, game.opponent_board.0))
+    .collect::<HashMap<_, _>>();
+
+    let moves = query!(
+        r#"SELECT shadow_move.game_id, shadow_move.strategy as "strategy: AttackStrategy", shadow_move.move_x, shadow_move.move_y, round.outcome as "outcome: MoveOutcome"
+        FROM shadow_move JOIN round ON round.game_id = shadow_move.game_id AND round.round_id = shadow_move.round_id"#
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let revealed_games = boards
+        .iter()
+        .filter(|(_, board)| {
+            board
+                .fields
+                .iter()
+                .flatten()
+                .any(|&field| field == FieldState::Ship)
+        })
+        .map(|(game_id, _)| game_id.clone())
+        .collect::<HashSet<_>>();
+
+    let mut stats: HashMap<AttackStrategy, ShadowStats> = HashMap::new();
+
+    for shadow_move in moves {
+        let board = match boards.get(&shadow_move.game_id) {
+            Some(board) => board,
+            None => continue,
+        };
+
+        let position = Position {
+            x: shadow_move.move_x as i8,
+            y: shadow_move.move_y as i8,
+        };
+
+        let stats = stats.entry(shadow_move.strategy).or_default();
+        stats.rounds += 1;
+
+        let revealed = revealed_games.contains(&shadow_move.game_id);
+
+        match board.get_field(position) {
+            FieldState::Unknown if !revealed => {
+                stats.unrevealed += 1;
+                continue;
+            }
+            FieldState::Unknown | FieldState::Empty => stats.misses += 1,
+            _ => stats.hits += 1,
+        }
+
+        match shadow_move.outcome {
+            Some(MoveOutcome::Miss) => stats.played_misses += 1,
+            Some(_) => stats.played_hits += 1,
+            None => {}
+        }
+    }
+
+    if stats.is_empty() {
+        info!("No shadow moves of finished games found");
+        return Ok(());
+    }
+
+    for (strategy, stats) in stats
+        .iter()
+        .sorted_by_key(|(strategy, _)| strategy.get_name())
+    {
+        info!(
+            "Strategy {}: {} rounds, would have hit {}, played moves hit {}, {} unrevealed",
+            strategy,
+            stats.rounds,
+            format_rate(stats.hits, stats.misses),
+            format_rate(stats.played_hits, stats.played_misses),
+            stats.unrevealed
+        );
+    }
+
+    Ok(())
+}
+
+fn format_rate(hits: usize, misses: usize) -> String {
+    match hits + misses {
+        0 => String::from("-"),
+        total => format!(
+            "{:.1}% ({}/{})",
+            hits as f64 * 100.0 / total as f64,
+            hits,
+            total
+        ),
+    }
+}
@@ -0,0 +1,120 @@
+use anyhow::Result;
+use log::info;
+use sqlx::query;
+
+use crate::application::database::DB_POOL;
+
+struct VariantStats {
+    name: String,
+    games: i64,
+    wins: i64,
+    losses: i64,
+    ties: i64,
+}
+
+impl VariantStats {
+    fn get_win_rate(&self) -> f64 {
+        self.wins as f64 / self.games as f64
+    }
+}
+
+/// Prints the win rate of every variant in finished games. Each variant is
+/// compared with the one that played the most games using a two-sided
+/// two-proportion z-test.
+pub async fn report_variants() -> Result<()> {
+    let pool = DB_POOL.get().unwrap();
+
+    let mut stats = query!(
+        r#"SELECT
+            COALESCE(variant, 'default') as "name!",
+            COUNT(*) as "games!",
+            COUNT(*) FILTER (WHERE result = 'win') as "wins!",
+            COUNT(*) FILTER (WHERE result = 'loss') as "losses!",
+            COUNT(*) FILTER (WHERE result = 'tie') as "ties!"
+        FROM game WHERE result IS NOT NULL GROUP BY 1"#
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|row| VariantStats {
+        name: row.name,
+        games: row.games,
+        wins: row.wins,
+        losses: row.losses,
+        ties: row.ties,
+    })
+    .collect::<Vec<_>>();
+
+    stats.sort_by(|a, b| b.games.cmp(&a.games).then_with(|| a.name.cmp(&b.name)));
+
+    let baseline = match stats.first() {
+        Some(baseline) => baseline,
+        None => {
+            info!("No finished games found");
+            return Ok(());
+        }
+    };
+
+    info!("Comparing with variant {}", baseline.name);
+
+    for variant in &stats {
+        let comparison = if variant.name == baseline.name {
+            String::from("baseline")
+        } else {
+            let p_value = get_p_value(baseline, variant);
+
+            format!(
+                "{:+.1} points, p = {:.3}{}",
+                (variant.get_win_rate() - baseline.get_win_rate()) * 100.0,
+                p_value,
+                if p_value < 0.05 { " (significant)" } else { "" }
+            )
+        };
+
+        info!(
+            "Variant {}: {} games, {} wins, {} losses, {} ties, win rate {:.1}%, {}",
+            variant.name,
+            variant.games,
+            variant.wins,
+            variant.losses,
+            variant.ties,
+            variant.get_win_rate() * 100.0,
+            comparison
+        );
+    }
+
+    Ok(())
+}
+
+/// Two-sided p-value for both variants having the same win rate.
+fn get_p_value(a: &VariantStats, b: &VariantStats) -> f64 {
+    let pooled = (a.wins + b.wins) as f64 / (a.games + b.games) as f64;
+    let error = (pooled * (1.0 - pooled) * (1.0 / a.games as f64 + 1.0 / b.games as f64)).sqrt();
+
+    if error == 0.0 {
+        return 1.0;
+    }
+
+    let z = (a.get_win_rate() - b.get_win_rate()).abs() / error;
+
+    2.0 * (1.0 - get_normal_cdf(z))
+}
+
+fn get_normal_cdf(x: f64) -> f64 {
+    0.5 * (1.0 + erf(x / std::f64::consts::SQRT_2))
+}
+
+/// Abramowitz and Stegun 7.1.26, accurate to about 1e-7.
+fn erf(x: f64) -> f64 {
+    let t = 1.0 / (1.0 + 0.3275911 * x.abs());
+    let polynomial = t
+        * (0.254829592
+            + t * (-0.284496736 + t * (1.421413741 + t * (-1.453152027 + t * 1.061405429))));
+    let value = 1.0 - polynomial * (-x * x).exp();
+
+    if x < 0.0 {
+        -value
+    } else {
+        value
+    }
+}
@@ -0,0 +1,79 @@
+use std::collections::HashMap;
+
+use rand_distr::{Beta, Distribution};
+use sqlx::query;
+
+use crate::application::database::DB_POOL;
+
+use super::variant::Variant;
+
+/// Results of the finished games a variant played
+#[derive(Debug, Default, Clone, Copy)]
+pub struct VariantRecord {
+    pub wins: i64,
+    pub losses: i64,
+    pub ties: i64,
+}
+
+/// Thompson sampling: draws a win rate for every variant from its Beta
+/// posterior and picks the best draw. Variants without games start from a
+/// uniform prior, so new ones still get played.
+pub fn sample_variant<'a>(
+    variants: &'a [Variant],
+    records: &HashMap<String, VariantRecord>,
+) -> Option<&'a Variant> {
+    let mut rng = rand::thread_rng();
+
+    variants
+        .iter()
+        .map(|variant| {
+            let record = records.get(&variant.name).copied().unwrap_or_default();
+
+            // Ties count as half a win
+            let alpha = 1.0 + record.wins as f64 + record.ties as f64 / 2.0;
+            let beta = 1.0 + record.losses as f64 + record.ties as f64 / 2.0;
+
+            let win_rate = Beta::new(alpha, beta)
+                .map(|distribution| distribution.sample(&mut rng))
+                .unwrap_or(0.0);
+
+            (variant, win_rate)
+        })
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(variant, _)| variant)
+}
+
+/// Loads the results of all finished games by variant, only against the
+/// given opponent if there is one.
+pub async fn load_variant_records(opponent_id: Option<&str>) -> HashMap<String, VariantRecord> {
+    let pool = match DB_POOL.get() {
+        Some(pool) => pool,
+        None => return HashMap::new(),
+    };
+
+    query!(
+        r#"SELECT
+            variant as "variant!",
+            COUNT(*) FILTER (WHERE result = 'win') as "wins!",
+            COUNT(*) FILTER (WHERE result = 'loss') as "losses!",
+            COUNT(*) FILTER (WHERE result = 'tie') as "ties!"
+        FROM game
+        WHERE result IS NOT NULL AND variant IS NOT NULL AND ($1::VARCHAR IS NULL OR opponent_id = $1)
+        GROUP BY variant"#,
+        opponent_id
+    )
+    .fetch_all(pool)
+    .await
+    .expect("Failed to load variant results from database")
+    .into_iter()
+    .map(|row| {
+        let record = VariantRecord {
+            wins: row.wins,
+            losses: row.losses,
+            ties: row.ties,
+        };
+
+        (row.variant, record)
+    })
+    .collect()
+}
@@ -0,0 +1,57 @@
+use std::time::Instant;
+
+use futures_util::{future::BoxFuture, FutureExt};
+use log::debug;
+use serde_json::Value;
+
+use crate::{
+    application::dashboard::{self, is_dashboard_enabled},
+    bot::GameBot,
+    network::models::GameEvent,
+};
+
+use super::{controller::handle_game_event, guard::get_fallback_response, session::remove_session};
+
+pub struct BattleshipBot;
+
+impl GameBot for BattleshipBot {
+    fn get_name(&self) -> &'static str {
+        "battleship"
+    }
+
+    fn handle_event(&self, event: Value) -> BoxFuture<'static, anyhow::Result<Option<Value>>> {
+        async move {
+            let event = serde_json::from_value::<GameEvent>(event)?;
+
+            debug!("Received event: {:#?}", event);
+
+            let game_id = event.get_details().map(|details| details.id.clone());
+            let started_at = Instant::now();
+
+            if is_dashboard_enabled() {
+                dashboard::record_event(&event);
+            }
+
+            let response = handle_game_event(event).await;
+
+            if let (true, Some(game_id)) = (is_dashboard_enabled(), &game_id) {
+                dashboard::record_response(game_id, response.as_ref(), started_at.elapsed()).await;
+            }
+
+            Ok(response.map(|response| serde_json::to_value(response).unwrap()))
+        }
+        .boxed()
+    }
+
+    fn get_fallback_response(&self, event: &Value) -> Option<Value> {
+        let event = serde_json::from_value::<GameEvent>(event.clone()).ok()?;
+
+        get_fallback_response(&event).map(|response| serde_json::to_value(response).unwrap())
+    }
+
+    fn drop_game(&self, game_id: &str) {
+        if remove_session(game_id).is_some() {
+            debug!("Dropped session of idle game {}", game_id);
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use log::warn;
+use sqlx::{Postgres, Transaction};
+
+use crate::{
+    game::{
+        logic::consistency::{Desync, DesyncKind},
+        session::GameSession,
+    },
+    network::models::{GameRoundEvent, Position},
+};
+
+/// Compares the boards of a round with our shot history and placement and
+/// records every desync not recorded in an earlier round, replacing those of
+/// a redelivered event. Only shots the log already contains are checked, a
+/// redelivered event finds its own attack stored but not applied yet.
+pub async fn check_consistency(
+    event: &GameRoundEvent,
+    session: &GameSession,
+    transaction: &mut Transaction<'_, Postgres>,
+) {
+    let game_id = &event.details.id;
+    let round_number = event.details.log.len() as i32;
+
+    let shots = sqlx::query!(
+        r#"SELECT move_x, move_y FROM round WHERE game_id = $1 AND opponent_move = false AND round_id < $2"#,
+        game_id,
+        round_number
+    )
+    .fetch_all(&mut **transaction)
+    .await
+    .expect("Failed to load rounds from database")
+    .into_iter()
+    .filter_map(|round| {
+        Some(Position {
+            x: round.move_x? as i8,
+            y: round.move_y? as i8,
+        })
+    })
+    .collect::<Vec<_>>();
+
+    let opponent_desyncs = event.get_opponent_board().check_shots(&shots);
+
+    let self_desyncs = match &session.placement {
+        Some(placement) => event.get_self_board().check_placement(placement),
+        None => Vec::new(),
+    };
+
+    sqlx::query!(
+        r#"DELETE FROM desync WHERE game_id = $1 AND round_number = $2"#,
+        game_id,
+        round_number,
+    )
+    .execute(&mut **transaction)
+    .await
+    .expect("Failed to delete desyncs from database");
+
+    let desyncs = opponent_desyncs
+        .into_iter()
+        .map(|desync| (true, desync))
+        .chain(self_desyncs.into_iter().map(|desync| (false, desync)));
+
+    for (opponent_board, desync) in desyncs {
+        let Desync {
+            kind,
+            position,
+            details,
+        } = desync;
+
+        let inserted = sqlx::query!(
+            r#"INSERT INTO desync (game_id, round_number, opponent_board, kind, x, y, details) VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (game_id, opponent_board, kind, x, y) DO NOTHING"#,
+            game_id,
+            round_number,
+            opponent_board,
+            kind as DesyncKind,
+            position.x as i16,
+            position.y as i16,
+            details,
+        )
+        .execute(&mut **transaction)
+        .await
+        .expect("Failed to insert desync in database")
+        .rows_affected();
+
+        // Desyncs stay on the board, only new ones are worth a warning
+        if inserted == 0 {
+            continue;
+        }
+
+        warn!(
+            "Desync in game {} on {} board: {:?} at {:?} ({})",
+            game_id,
+            if opponent_board { "opponent" } else { "own" },
+            kind,
+            position,
+            details
+        );
+    }
+}
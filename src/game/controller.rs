@@ -1,27 +1,59 @@
 use crate::network::models::GameEvent;
 use crate::network::models::GameResponse;
 
-use super::handler::{init_handler, result_handler, round_handler, set_handler};
+use super::guard::guard_placement;
+use super::handler::{init_handler, result_handler, round_handler, set_handler, unknown_handler};
+use super::resume::get_or_resume_session;
+use super::session::remove_session;
 
 pub async fn handle_game_event(event: GameEvent) -> Option<GameResponse> {
     match event {
         GameEvent::Init(event) => {
-            init_handler(event).await;
+            let session = get_or_resume_session(&event.details, None, None).await;
+
+            init_handler(event, &*session.lock().await).await;
             None
         }
         GameEvent::Round(event) => {
-            let position = round_handler(event).await;
+            let session = get_or_resume_session(
+                &event.details,
+                Some(event.get_opponent_board()),
+                Some(event.get_self_board()),
+            )
+            .await;
+            let mut session = session.lock().await;
+            session.touch();
+
+            let position = round_handler(event, &mut session).await?;
 
             Some(GameResponse::Attack(position))
         }
         GameEvent::Result(event) => {
-            result_handler(event).await;
+            let game_id = event.details.id.clone();
+            let session = get_or_resume_session(
+                &event.details,
+                event.get_opponent_board(),
+                event.get_self_board(),
+            )
+            .await;
+
+            result_handler(event, &mut *session.lock().await).await;
+
+            remove_session(&game_id);
             None
         }
         GameEvent::Set(event) => {
-            let placement = set_handler(event).await;
+            let game_id = event.details.id.clone();
+
+            let session = get_or_resume_session(&event.details, None, None).await;
+            let mut session = session.lock().await;
+            session.touch();
+
+            let placement = set_handler(event, &mut session).await;
+            let placement = guard_placement(&game_id, placement, &mut session);
 
             Some(GameResponse::PlaceShips(placement))
         }
+        GameEvent::Unknown(event) => unknown_handler(event).await.map(GameResponse::Raw),
     }
 }
@@ -0,0 +1,103 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use log::{error, warn};
+
+use rand::seq::SliceRandom;
+
+use crate::network::models::{Board, FieldState, GameEvent, GameResponse, Placement, Position};
+
+use super::{handler::get_default_ack, logic::heatmap::Heatmap, session::GameSession};
+
+/// Number of responses the guard had to replace since the start
+static INTERVENTIONS: AtomicUsize = AtomicUsize::new(0);
+
+pub fn get_intervention_count() -> usize {
+    INTERVENTIONS.load(Ordering::Relaxed)
+}
+
+/// Makes sure we only attack unknown fields on the board. Invalid or missing
+/// attacks are replaced by the hottest unknown field, `None` is only returned
+/// if there is no unknown field left.
+pub fn guard_attack(
+    game_id: &str,
+    board: &Board,
+    position: Option<Position>,
+    heatmap: Option<&Heatmap>,
+) -> Option<Position> {
+    if let Some(position) = position {
+        if board.get_rect().contains(position) && board.get_field(position) == FieldState::Unknown {
+            return Some(position);
+        }
+    }
+
+    let fallback = board
+        .find_fields(vec![FieldState::Unknown], |_| true)
+        .into_iter()
+        .max_by_key(|field| match heatmap {
+            Some(heatmap) => heatmap.fields[field.x as usize][field.y as usize],
+            None => 0,
+        });
+
+    let fallback = match fallback {
+        Some(fallback) => fallback,
+        None => {
+            error!("No field left to attack in game {}", game_id);
+            return None;
+        }
+    };
+
+    INTERVENTIONS.fetch_add(1, Ordering::Relaxed);
+
+    match position {
+        Some(position) => warn!(
+            "Replaced invalid attack at {} with {} in game {}",
+            position, fallback, game_id
+        ),
+        None => warn!("Attacking {} as fallback in game {}", fallback, game_id),
+    }
+
+    Some(fallback)
+}
+
+/// Makes sure the placement follows the fleet rules, an invalid one is
+/// replaced by a random valid placement.
+pub fn guard_placement(
+    game_id: &str,
+    placement: Placement,
+    session: &mut GameSession,
+) -> Placement {
+    let errors = placement.validate();
+
+    if errors.is_empty() {
+        return placement;
+    }
+
+    INTERVENTIONS.fetch_add(1, Ordering::Relaxed);
+
+    for err in errors {
+        warn!("Invalid placement in game {}: {}", game_id, err);
+    }
+
+    let fallback: Placement = rand::random();
+
+    warn!("Replaced placement with {} in game {}", fallback, game_id);
+
+    session.placement = Some(fallback.clone());
+
+    fallback
+}
+
+/// Response for an event whose handling panicked. Only uses the event itself,
+/// the session may be in any state after a panic.
+pub fn get_fallback_response(event: &GameEvent) -> Option<GameResponse> {
+    match event {
+        GameEvent::Round(event) => event
+            .find_opponent_board()?
+            .find_fields(vec![FieldState::Unknown], |_| true)
+            .choose(&mut rand::thread_rng())
+            .map(|&position| GameResponse::Attack(position)),
+        GameEvent::Set(_) => Some(GameResponse::PlaceShips(rand::random())),
+        GameEvent::Unknown(_) => get_default_ack().map(GameResponse::Raw),
+        GameEvent::Init(_) | GameEvent::Result(_) => None,
+    }
+}
@@ -2,8 +2,10 @@ mod init_handler;
 mod result_handler;
 mod round_handler;
 mod set_handler;
+mod unknown_handler;
 
 pub use init_handler::*;
 pub use result_handler::*;
 pub use round_handler::*;
 pub use set_handler::*;
+pub use unknown_handler::*;
@@ -1,7 +1,13 @@
-use log::{error, info};
-use sqlx::query;
+use log::{debug, error, info};
+use sqlx::{query, types::Json};
 
-use crate::{application::database::DB_POOL, network::models::GameResultEvent};
+use crate::{
+    application::database::DB_POOL,
+    game::session::GameSession,
+    network::models::{Board, GameResultEvent},
+};
+
+use super::record_log;
 
 #[derive(sqlx::Type)]
 #[sqlx(rename_all = "lowercase")]
@@ -11,7 +17,7 @@ enum GameResult {
     Tie,
 }
 
-pub async fn result_handler(event: GameResultEvent) {
+pub async fn result_handler(event: GameResultEvent, session: &mut GameSession) {
     let self_player = event.details.get_self();
     let opponent = event.details.get_opponent();
 
@@ -34,9 +40,9 @@ pub async fn result_handler(event: GameResultEvent) {
             info!("Lost game {} after {} rounds!", game_id, rounds);
 
             if let Some(last_round) = event.details.log.last() {
+                // The error is stored with its round below
                 if let Some(error) = &last_round.error {
                     error!("Game {} ended with error: {}", game_id, error);
-                    return;
                 }
             }
         }
@@ -45,14 +51,46 @@ pub async fn result_handler(event: GameResultEvent) {
         }
     }
 
-    let pool = DB_POOL.get().unwrap();
+    if let Some(duration) = session.get_average_move_duration() {
+        debug!(
+            "Game {} took {:?} with {:?} per move on average",
+            game_id,
+            session.started_at.elapsed(),
+            duration
+        );
+    }
+
+    let pool = match DB_POOL.get() {
+        Some(pool) => pool,
+        None => return,
+    };
+
+    let mut transaction = pool.begin().await.expect("Failed to start transaction");
+
+    record_log(
+        &mut transaction,
+        &event.details,
+        session,
+        event.get_opponent_board(),
+        event.get_self_board(),
+    )
+    .await;
+
+    // Keep the revealed board for exports and reports
+    let opponent_board = event.get_opponent_board().map(Json);
 
     query!(
-        "UPDATE game SET ended_at = NOW(), result = $1 WHERE game_id = $2",
+        "UPDATE game SET ended_at = NOW(), result = $1, opponent_board = $2 WHERE game_id = $3",
         result as GameResult,
+        opponent_board as Option<Json<&Board>>,
         game_id
     )
-    .execute(pool)
+    .execute(&mut *transaction)
     .await
     .expect("Failed to update game in database");
+
+    transaction
+        .commit()
+        .await
+        .expect("Failed to commit result in database");
 }
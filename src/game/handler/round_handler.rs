@@ -1,178 +1,253 @@
-use itertools::Itertools;
-use log::debug;
+use std::time::Instant;
+
+use log::{debug, trace};
+use sqlx::{types::Json, Postgres, Transaction};
 
 use crate::{
     application::database::DB_POOL,
-    game::logic::heatmap::Heatmap,
-    network::models::{Board, FieldState, GameRoundEvent, Position},
+    game::{
+        consistency::check_consistency,
+        guard::guard_attack,
+        logic::{
+            board_diff::{BoardChange, MoveOutcome},
+            explanation::MoveExplanation,
+            heatmap::Heatmap,
+            render::BoardRenderer,
+            strategy::AttackStrategy,
+        },
+        session::GameSession,
+        shadow::{get_shadow_moves, ShadowMove},
+    },
+    network::models::{Board, GameDetails, GameRoundEvent, Position},
 };
 
-pub async fn round_handler(event: GameRoundEvent) -> Position {
-    let pool = DB_POOL.get().unwrap();
-    let opponent = event.details.get_opponent();
+/// Chooses our next attack, `None` if there is no field left to attack. The
+/// new rounds of the log and our attack are stored in one transaction, so a
+/// redelivered event overwrites its rows instead of adding new ones.
+pub async fn round_handler(event: GameRoundEvent, session: &mut GameSession) -> Option<Position> {
+    let started_at = Instant::now();
+    let game_id = &event.details.id;
 
-    for round in event
-        .details
-        .log
-        .iter()
-        .rev()
-        .take_while(|round| round.player == opponent.id)
-    {
-        let move_x = match round.game_move {
-            Some(position) => Some(position.x as i16),
-            None => None,
-        };
+    let strategy = session.variant.attack;
 
-        let move_y = match round.game_move {
-            Some(position) => Some(position.y as i16),
-            None => None,
-        };
+    let opponent_board = event.get_opponent_board();
+    let (heatmap, heatmap_state) =
+        Heatmap::compute_incremental(session.heatmap_state.take(), opponent_board.clone()).await;
 
-        sqlx::query!(
-            r#"INSERT INTO round (game_id, opponent_move, move_x, move_y) VALUES ($1, $2, $3, $4)"#,
-            event.details.id,
-            true,
-            move_x,
-            move_y,
-        )
-        .execute(pool)
-        .await
-        .expect("Failed to insert round in database");
+    let mut explanation = strategy.explain(opponent_board, &heatmap);
+    explanation.set_duration(started_at.elapsed());
+
+    debug!("Attack in game {}: {}", game_id, explanation);
+
+    let position = guard_attack(
+        game_id,
+        opponent_board,
+        explanation.position,
+        Some(&heatmap),
+    );
+
+    // Attacks the guard sent instead are stored without strategy
+    let chosen_strategy = if position == explanation.position {
+        Some(strategy)
+    } else {
+        None
+    };
+
+    if let Some(position) = position {
+        trace!(
+            "Board of game {}:\n{}",
+            game_id,
+            BoardRenderer::new()
+                .self_board(event.get_self_board())
+                .opponent_board(opponent_board)
+                .heatmap(&heatmap)
+                .shot(position)
+                .render()
+        );
     }
 
-    let strategies = vec![next_attack, second_attack, first_attack];
+    if let Some(pool) = DB_POOL.get() {
+        let mut transaction = pool.begin().await.expect("Failed to start transaction");
 
-    let opponent_board = event.get_opponent_board();
-    let heatmap = Heatmap::from_board(opponent_board);
+        check_consistency(&event, session, &mut transaction).await;
 
-    debug!("Heatmap for game {}: {:#?}", event.details.id, heatmap);
+        record_log(
+            &mut transaction,
+            &event.details,
+            session,
+            Some(opponent_board),
+            Some(event.get_self_board()),
+        )
+        .await;
 
-    let position = strategies
-        .iter()
-        .enumerate()
-        .find_map(|(index, strategy)| {
-            let position = strategy(&opponent_board)
-                .into_iter()
-                .sorted_by(|a, b| {
-                    let a_heat = heatmap.fields[a.x as usize][a.y as usize];
-                    let b_heat = heatmap.fields[b.x as usize][b.y as usize];
-
-                    b_heat.cmp(&a_heat)
-                })
-                .next()?;
+        if let Some(position) = position {
+            let explanation = chosen_strategy.map(|_| &explanation);
+            let shadows = get_shadow_moves(strategy, opponent_board, &heatmap);
 
-            debug!(
-                "Using strategy {} to attack at {:?} in game {}",
-                index + 1,
+            record_attack(
+                &mut transaction,
+                game_id,
+                event.details.log.len() as i32,
                 position,
-                event.details.id
-            );
+                explanation,
+                &heatmap,
+                &shadows,
+            )
+            .await;
+        }
+
+        transaction
+            .commit()
+            .await
+            .expect("Failed to commit round in database");
+    }
 
-            Some(position)
-        })
-        .expect("No strategy returned a position");
+    session.self_board = Some(event.get_self_board().clone());
+    session.opponent_board = Some(opponent_board.clone());
+    session.heatmap = Some(heatmap);
+    session.heatmap_state = Some(heatmap_state);
+    session.log_len = event.details.log.len();
 
-    let pool = DB_POOL.get().unwrap();
+    if let Some(position) = position {
+        session.record_move(position, chosen_strategy, started_at.elapsed());
+    }
+
+    position
+}
+
+/// Stores our attack as the next round of the log. Strategy, heat and
+/// explanation are only stored if the strategy chose the attack.
+async fn record_attack(
+    transaction: &mut Transaction<'_, Postgres>,
+    game_id: &str,
+    round_id: i32,
+    position: Position,
+    explanation: Option<&MoveExplanation>,
+    heatmap: &Heatmap,
+    shadows: &[ShadowMove],
+) {
+    let heat = explanation.map(|_| heatmap.fields[position.x as usize][position.y as usize] as i32);
 
     sqlx::query!(
-        r#"INSERT INTO round (game_id, opponent_move, move_x, move_y) VALUES ($1, $2, $3, $4)"#,
-        event.details.id,
+        r#"INSERT INTO round (game_id, round_id, opponent_move, move_x, move_y, strategy, heat, explanation) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        ON CONFLICT (game_id, round_id) DO UPDATE SET move_x = EXCLUDED.move_x, move_y = EXCLUDED.move_y, strategy = EXCLUDED.strategy, heat = EXCLUDED.heat, explanation = EXCLUDED.explanation"#,
+        game_id,
+        round_id,
         false,
         position.x as i16,
         position.y as i16,
+        explanation.map(|explanation| explanation.strategy) as Option<AttackStrategy>,
+        heat,
+        explanation.map(Json) as _,
     )
-    .execute(pool)
+    .execute(&mut **transaction)
     .await
-    .expect("Failed to insert round in database");
+    .expect("Failed to store round in database");
 
-    position
-}
+    sqlx::query!(
+        r#"DELETE FROM shadow_move WHERE game_id = $1 AND round_id = $2"#,
+        game_id,
+        round_id,
+    )
+    .execute(&mut **transaction)
+    .await
+    .expect("Failed to delete shadow moves from database");
 
-fn first_attack(board: &Board) -> Vec<Position> {
-    board.find_fields(vec![FieldState::Unknown], |position| {
-        position.x % 2 == position.y % 2 && !board.is_occupied(*position)
-    })
+    for shadow in shadows {
+        sqlx::query!(
+            r#"INSERT INTO shadow_move (game_id, round_id, strategy, move_x, move_y, heat) VALUES ($1, $2, $3, $4, $5, $6)"#,
+            game_id,
+            round_id,
+            shadow.strategy as AttackStrategy,
+            shadow.position.x as i16,
+            shadow.position.y as i16,
+            shadow.heat as i32,
+        )
+        .execute(&mut **transaction)
+        .await
+        .expect("Failed to insert shadow move in database");
+    }
 }
 
-fn second_attack(board: &Board) -> Vec<Position> {
-    let damaged_field = match board.find_field(vec![FieldState::Damaged], |_| true) {
-        Some(field) => field,
-        None => return Vec::new(),
+/// Stores the rounds of the log added since the previous event, keyed by
+/// their index, with their errors and the outcomes the boards show. A new
+/// session stores the whole log.
+///
+/// Outcomes are only visible on the boards right after a move, so both
+/// boards have to be the ones of the event and the session still has those
+/// of the previous event. Outcomes stored before are never cleared.
+pub async fn record_log(
+    transaction: &mut Transaction<'_, Postgres>,
+    details: &GameDetails,
+    session: &GameSession,
+    opponent_board: Option<&Board>,
+    self_board: Option<&Board>,
+) {
+    let get_changes = |previous: &Option<Board>, next: Option<&Board>| match (previous, next) {
+        (Some(previous), Some(next)) => previous.diff(next),
+        _ => Vec::new(),
     };
 
-    let neighbor_fields = vec![
-        Position {
-            x: damaged_field.x - 1,
-            y: damaged_field.y,
-        },
-        Position {
-            x: damaged_field.x + 1,
-            y: damaged_field.y,
-        },
-        Position {
-            x: damaged_field.x,
-            y: damaged_field.y - 1,
-        },
-        Position {
-            x: damaged_field.x,
-            y: damaged_field.y + 1,
-        },
-    ];
+    let opponent_changes = get_changes(&session.opponent_board, opponent_board);
+    let self_changes = get_changes(&session.self_board, self_board);
 
-    neighbor_fields
-        .into_iter()
-        .filter(|&position| board.check_field(position, vec![FieldState::Unknown]))
-        .collect_vec()
-}
+    let last_attack = details
+        .log
+        .iter()
+        .rposition(|round| round.player == details.self_id);
 
-fn next_attack(board: &Board) -> Vec<Position> {
-    let damaged_fields = board.find_fields(vec![FieldState::Damaged], |_| true);
+    // Stored attacks have to be updated with their outcome
+    let first_index = match last_attack {
+        Some(last_attack) => session.log_len.min(last_attack),
+        None => session.log_len,
+    };
 
-    if damaged_fields.len() == 1 {
-        return Vec::new();
-    }
+    for (index, round) in details.log.iter().enumerate().skip(first_index) {
+        let opponent_move = round.player != details.self_id;
 
-    let first_damaged_field = match damaged_fields.first() {
-        Some(field) => field,
-        None => return Vec::new(),
-    };
+        // Only our last attack and the opponent moves after it changed the
+        // boards since the previous event
+        let changes: &[BoardChange] = match last_attack {
+            Some(last_attack) if !opponent_move && index == last_attack => &opponent_changes,
+            Some(last_attack) if opponent_move && index > last_attack => &self_changes,
+            None if opponent_move => &self_changes,
+            _ => &[],
+        };
 
-    let last_damaged_field = match damaged_fields.last() {
-        Some(field) => field,
-        None => return Vec::new(),
-    };
+        let outcome = round.game_move.and_then(|position| {
+            changes
+                .iter()
+                .find(|change| change.get_position() == position)
+                .map(|change| change.get_outcome())
+        });
 
-    if first_damaged_field.x != last_damaged_field.x {
-        let neighbor_fields = vec![
-            Position {
-                x: first_damaged_field.x - 1,
-                y: first_damaged_field.y,
-            },
-            Position {
-                x: last_damaged_field.x + 1,
-                y: last_damaged_field.y,
-            },
-        ];
-
-        neighbor_fields
-            .into_iter()
-            .filter(|&position| board.check_field(position, vec![FieldState::Unknown]))
-            .collect_vec()
-    } else {
-        let neighbor_fields = vec![
-            Position {
-                x: first_damaged_field.x,
-                y: first_damaged_field.y - 1,
-            },
-            Position {
-                x: last_damaged_field.x,
-                y: last_damaged_field.y + 1,
-            },
-        ];
-
-        neighbor_fields
-            .into_iter()
-            .filter(|&position| board.check_field(position, vec![FieldState::Unknown]))
-            .collect_vec()
+        if let (Some(position), Some(outcome)) = (round.game_move, outcome) {
+            debug!(
+                "{} at {} in game {}: {:?}",
+                if opponent_move {
+                    "Opponent attacked"
+                } else {
+                    "Attacked"
+                },
+                position,
+                details.id,
+                outcome
+            );
+        }
+
+        sqlx::query!(
+            r#"INSERT INTO round (game_id, round_id, opponent_move, move_x, move_y, error, outcome) VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (game_id, round_id) DO UPDATE SET opponent_move = EXCLUDED.opponent_move, move_x = EXCLUDED.move_x, move_y = EXCLUDED.move_y, error = EXCLUDED.error, outcome = COALESCE(EXCLUDED.outcome, round.outcome)"#,
+            details.id,
+            index as i32,
+            opponent_move,
+            round.game_move.map(|position| position.x as i16),
+            round.game_move.map(|position| position.y as i16),
+            round.error,
+            outcome as Option<MoveOutcome>,
+        )
+        .execute(&mut **transaction)
+        .await
+        .expect("Failed to store round in database");
     }
 }
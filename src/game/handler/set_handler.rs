@@ -1,7 +1,14 @@
-use crate::network::models::{GameSetEvent, Placement};
+use crate::{
+    application::thread_pool::spawn_compute,
+    game::session::GameSession,
+    network::models::{GameSetEvent, Placement},
+};
 
-pub async fn set_handler(_event: GameSetEvent) -> Placement {
-    let placement: Placement = rand::random();
+pub async fn set_handler(_event: GameSetEvent, session: &mut GameSession) -> Placement {
+    let strategy = session.variant.placement;
+    let placement = spawn_compute(move || strategy.choose()).await;
+
+    session.placement = Some(placement.clone());
 
     placement
 }
@@ -0,0 +1,36 @@
+use log::{error, warn};
+use serde_json::Value;
+use sqlx::{query, types::Json};
+
+use crate::{application::database::DB_POOL, network::models::UnknownGameEvent};
+
+/// Records an event type we don't know and returns the configured default
+/// ack (`UNKNOWN_EVENT_ACK`), `None` to not ack if unset.
+pub async fn unknown_handler(event: UnknownGameEvent) -> Option<Value> {
+    warn!("Received unknown event type {}", event.event_type);
+
+    if let Some(pool) = DB_POOL.get() {
+        query!(
+            "INSERT INTO unknown_event (event_type, payload) VALUES ($1, $2)",
+            event.event_type,
+            Json(&event.payload) as _,
+        )
+        .execute(pool)
+        .await
+        .expect("Failed to insert unknown event into database");
+    }
+
+    get_default_ack()
+}
+
+pub fn get_default_ack() -> Option<Value> {
+    let ack = std::env::var("UNKNOWN_EVENT_ACK").ok()?;
+
+    if ack.is_empty() {
+        return None;
+    }
+
+    serde_json::from_str(&ack)
+        .map_err(|err| error!("Invalid UNKNOWN_EVENT_ACK {}: {}", ack, err))
+        .ok()
+}
@@ -1,12 +1,41 @@
-use prettytable::{Cell, Row, Table};
+use log::debug;
 
 use crate::network::models::{
     Board, BoardOrBool, Direction, FieldState, Placement, Position, Ship,
 };
 
+use super::{
+    grid::{Rect, BOARD_SIZE},
+    placement::PlacementError,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DetectionError {
+    /// Ship field without any direction the ship could extend in
+    NoDirection(Position),
+    /// Ship field left after the whole fleet was detected
+    TooManyShips(Position),
+    /// Detected ship breaks the fleet rules
+    InvalidShip(PlacementError),
+}
+
 impl Board {
+    /// Board without any revealed fields
+    pub fn new() -> Board {
+        Board {
+            fields: vec![vec![FieldState::Unknown; BOARD_SIZE as usize]; BOARD_SIZE as usize],
+        }
+    }
+
+    pub fn get_rect(&self) -> Rect {
+        let width = self.fields.len() as i8;
+        let height = self.fields.first().map_or(0, |col| col.len()) as i8;
+
+        Rect::with_size(width, height)
+    }
+
     pub fn get_field(&self, position: Position) -> FieldState {
-        if position.x < 0 || position.y < 0 || position.x > 9 || position.y > 9 {
+        if !self.get_rect().contains(position) {
             return FieldState::Empty;
         }
 
@@ -14,7 +43,7 @@ impl Board {
     }
 
     pub fn set_field(&mut self, position: Position, state: FieldState) {
-        if position.x < 0 || position.y < 0 || position.x > 9 || position.y > 9 {
+        if !self.get_rect().contains(position) {
             return;
         }
 
@@ -26,51 +55,12 @@ impl Board {
     }
 
     pub fn is_occupied(&self, position: Position) -> bool {
-        let neighbor_fields = vec![
-            Position {
-                x: position.x - 1,
-                y: position.y - 1,
-            },
-            Position {
-                x: position.x,
-                y: position.y - 1,
-            },
-            Position {
-                x: position.x + 1,
-                y: position.y - 1,
-            },
-            Position {
-                x: position.x - 1,
-                y: position.y,
-            },
-            Position {
-                x: position.x + 1,
-                y: position.y,
-            },
-            Position {
-                x: position.x - 1,
-                y: position.y + 1,
-            },
-            Position {
-                x: position.x,
-                y: position.y + 1,
-            },
-            Position {
-                x: position.x + 1,
-                y: position.y + 1,
-            },
-        ];
-
-        for neighbor_field in neighbor_fields {
-            if self.check_field(
+        position.get_surrounding().any(|neighbor_field| {
+            self.check_field(
                 neighbor_field,
                 vec![FieldState::Ship, FieldState::Destroyed, FieldState::Damaged],
-            ) {
-                return true;
-            }
-        }
-
-        false
+            )
+        })
     }
 
     pub fn find_fields<CB: Fn(&Position) -> bool>(
@@ -125,97 +115,61 @@ impl Board {
         None
     }
 
+    pub fn get_changed_fields(&self, other: &Board) -> Vec<Position> {
+        let mut positions = Vec::new();
+
+        for (x, (col, other_col)) in self.fields.iter().zip(&other.fields).enumerate() {
+            for (y, (field, other_field)) in col.iter().zip(other_col).enumerate() {
+                if field != other_field {
+                    positions.push(Position {
+                        x: x as i8,
+                        y: y as i8,
+                    });
+                }
+            }
+        }
+
+        positions
+    }
+
     fn detect_direction(&self, start: &Position) -> Vec<Direction> {
+        let ship_states = vec![FieldState::Ship, FieldState::Destroyed, FieldState::Damaged];
+
         // Check if ship is horizontal
-        if self.check_field(
-            Position {
-                x: start.x + 1,
-                y: start.y,
-            },
-            vec![FieldState::Ship, FieldState::Destroyed, FieldState::Damaged],
-        ) {
+        if self.check_field(start.moved(Direction::Horizontal, 1), ship_states.clone()) {
             return vec![Direction::Horizontal];
         }
 
         // Check if ship is vertical
-        if self.check_field(
-            Position {
-                x: start.x,
-                y: start.y + 1,
-            },
-            vec![FieldState::Ship, FieldState::Destroyed, FieldState::Damaged],
-        ) {
+        if self.check_field(start.moved(Direction::Vertical, 1), ship_states) {
             return vec![Direction::Vertical];
         }
 
         // Check possible directions
-        let mut possible_directions: Vec<Direction> = vec![];
-
-        if self.check_field(
-            Position {
-                x: start.x + 1,
-                y: start.y,
-            },
-            vec![FieldState::Unknown],
-        ) || self.check_field(
-            Position {
-                x: start.x - 1,
-                y: start.y,
-            },
-            vec![FieldState::Unknown],
-        ) {
-            possible_directions.push(Direction::Horizontal);
-        }
-
-        if self.check_field(
-            Position {
-                x: start.x,
-                y: start.y + 1,
-            },
-            vec![FieldState::Unknown],
-        ) || self.check_field(
-            Position {
-                x: start.x,
-                y: start.y - 1,
-            },
-            vec![FieldState::Unknown],
-        ) {
-            possible_directions.push(Direction::Vertical);
-        }
-
-        possible_directions
+        [Direction::Horizontal, Direction::Vertical]
+            .into_iter()
+            .filter(|&direction| {
+                self.check_field(start.moved(direction, 1), vec![FieldState::Unknown])
+                    || self.check_field(start.moved(direction, -1), vec![FieldState::Unknown])
+            })
+            .collect()
     }
 
     fn detect_size(&self, start: &Position, direction: &Direction, max_size: &i8) -> i8 {
-        let mut size: i8 = 1;
-
-        while size <= *max_size {
-            size += 1;
-
-            let x = start.x
-                + if *direction == Direction::Horizontal {
-                    size - 1
-                } else {
-                    0
-                };
-            let y = start.y
-                + if *direction == Direction::Vertical {
-                    size - 1
-                } else {
-                    0
-                };
-
-            let position = Position { x, y };
-
-            if !self.check_field(
-                position,
-                vec![FieldState::Ship, FieldState::Destroyed, FieldState::Damaged],
-            ) {
-                break;
-            }
-        }
+        let (x, y) = direction.get_step();
+
+        let size = start
+            .walk(x, y)
+            .take_while(|&position| {
+                self.check_field(
+                    position,
+                    vec![FieldState::Ship, FieldState::Destroyed, FieldState::Damaged],
+                )
+            })
+            .take((max_size - 1).max(0) as usize)
+            .count();
 
-        size - 1
+        size as i8 + 1
     }
 
     fn detect_unknown_fields_end(
@@ -225,32 +179,12 @@ impl Board {
         start: &Position,
         direction: &Direction,
     ) -> i8 {
-        let mut unknown_end = 0;
-
-        while unknown_end <= (max_size - size) {
-            unknown_end += 1;
-
-            let position = Position {
-                x: start.x
-                    + if *direction == Direction::Horizontal {
-                        size + unknown_end - 1
-                    } else {
-                        0
-                    },
-                y: start.y
-                    + if *direction == Direction::Vertical {
-                        size + unknown_end - 1
-                    } else {
-                        0
-                    },
-            };
+        let (x, y) = direction.get_step();
 
-            if !self.check_field(position, vec![FieldState::Unknown]) {
-                break;
-            }
-        }
-
-        unknown_end - 1
+        self.count_unknown_fields(
+            start.moved(*direction, size - 1).walk(x, y),
+            max_size - size,
+        )
     }
 
     fn detect_unknown_fields_start(
@@ -260,40 +194,29 @@ impl Board {
         start: &Position,
         direction: &Direction,
     ) -> i8 {
-        let mut unknown_start = 0;
-
-        while unknown_start <= (max_size - size) {
-            unknown_start += 1;
-
-            let position = Position {
-                x: start.x
-                    - (if *direction == Direction::Horizontal {
-                        unknown_start
-                    } else {
-                        0
-                    }),
-                y: start.y
-                    - (if *direction == Direction::Vertical {
-                        unknown_start
-                    } else {
-                        0
-                    }),
-            };
+        let (x, y) = direction.get_step();
 
-            if !self.check_field(position, vec![FieldState::Unknown]) {
-                break;
-            }
-        }
+        self.count_unknown_fields(start.walk(-x, -y), max_size - size)
+    }
 
-        unknown_start - 1
+    /// Counts the unknown fields at the start of the line, at most one more
+    /// than the missing size.
+    fn count_unknown_fields(&self, line: impl Iterator<Item = Position>, missing_size: i8) -> i8 {
+        line.take_while(|&position| self.check_field(position, vec![FieldState::Unknown]))
+            .take((missing_size + 1).max(0) as usize)
+            .count() as i8
     }
 
-    pub fn detect_complete_ships(&self) -> Placement {
+    /// Detects the completely revealed ships. Fields that don't form a valid
+    /// ship are skipped and reported, e.g. if the server shows touching ships.
+    pub fn detect_complete_ships(&self) -> (Placement, Vec<DetectionError>) {
         let mut placement = Placement::new();
+        let mut errors = Vec::new();
+        let mut skipped_fields: Vec<Position> = Vec::new();
 
         loop {
             let start = self.find_field(vec![FieldState::Ship, FieldState::Destroyed], |field| {
-                !placement.is_occupied(field)
+                !placement.is_occupied(field) && !skipped_fields.contains(field)
             });
 
             let start = match start {
@@ -301,23 +224,37 @@ impl Board {
                 None => break,
             };
 
-            let direction = self.detect_direction(&start);
-            let size = self.detect_size(
-                &start,
-                &direction[0],
-                &placement.get_missing_ship_sizes()[0],
-            );
+            let direction = match self.detect_direction(&start).first() {
+                Some(direction) => *direction,
+                None => {
+                    errors.push(DetectionError::NoDirection(start));
+                    skipped_fields.push(start);
+                    continue;
+                }
+            };
+
+            let max_size = match placement.get_missing_ship_sizes().first() {
+                Some(size) => *size,
+                None => {
+                    errors.push(DetectionError::TooManyShips(start));
+                    skipped_fields.push(start);
+                    continue;
+                }
+            };
 
             let ship = Ship {
                 start,
-                direction: direction[0],
-                size,
+                direction,
+                size: self.detect_size(&start, &direction, &max_size),
             };
 
-            placement.add_ship(ship).expect("Invalid ship detected");
+            if let Err(err) = placement.add_ship(ship) {
+                errors.push(DetectionError::InvalidShip(err));
+                skipped_fields.extend(ship.get_occupied_fields());
+            }
         }
 
-        placement
+        (placement, errors)
     }
 
     pub fn detect_damaged_ship(&self, placement: &Placement) -> Option<Vec<Placement>> {
@@ -352,23 +289,8 @@ impl Board {
                 let offset_max = std::cmp::min(missing_fields, unknown_start);
 
                 for offset in offset_min..=offset_max {
-                    let new_start = Position {
-                        x: start.x
-                            - (if direction == Direction::Horizontal {
-                                offset
-                            } else {
-                                0
-                            }),
-                        y: start.y
-                            - (if direction == Direction::Vertical {
-                                offset
-                            } else {
-                                0
-                            }),
-                    };
-
                     let ship = Ship {
-                        start: new_start,
+                        start: start.moved(direction, -offset),
                         direction,
                         size: *size,
                     };
@@ -390,36 +312,14 @@ impl Board {
     }
 
     pub fn detect_placements(&self) -> Vec<Placement> {
-        let placement = self.detect_complete_ships();
+        let (placement, errors) = self.detect_complete_ships();
 
-        self.detect_damaged_ship(&placement)
-            .unwrap_or_else(|| vec![placement])
-    }
-
-    pub fn print(&self) {
-        let board: Vec<Vec<&str>> = self
-            .fields
-            .iter()
-            .map(|col| {
-                col.iter()
-                    .map(|field| match field {
-                        FieldState::Empty => ".",
-                        FieldState::Unknown => " ",
-                        FieldState::Ship => "O",
-                        FieldState::Damaged => "x",
-                        FieldState::Destroyed => "X",
-                    })
-                    .collect()
-            })
-            .collect();
-
-        let mut table = Table::new();
-
-        for row in board {
-            table.add_row(Row::new(row.iter().map(|field| Cell::new(field)).collect()));
+        if !errors.is_empty() {
+            debug!("Ignoring undetectable ships: {:?}", errors);
         }
 
-        table.printstd();
+        self.detect_damaged_ship(&placement)
+            .unwrap_or_else(|| vec![placement])
     }
 }
 
@@ -0,0 +1,125 @@
+use itertools::Itertools;
+
+use crate::network::models::{Board, FieldState, Position};
+
+#[derive(sqlx::Type, Debug, Clone, Copy, PartialEq, Eq)]
+#[sqlx(type_name = "moveoutcome", rename_all = "lowercase")]
+pub enum MoveOutcome {
+    Miss,
+    Hit,
+    Sink,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BoardChange {
+    Miss(Position),
+    Hit(Position),
+    /// Shot that sank a ship, together with all fields of the sunk ship
+    Sink {
+        position: Position,
+        ship: Vec<Position>,
+    },
+}
+
+impl BoardChange {
+    pub fn get_position(&self) -> Position {
+        match self {
+            BoardChange::Miss(position) => *position,
+            BoardChange::Hit(position) => *position,
+            BoardChange::Sink { position, .. } => *position,
+        }
+    }
+
+    pub fn get_outcome(&self) -> MoveOutcome {
+        match self {
+            BoardChange::Miss(_) => MoveOutcome::Miss,
+            BoardChange::Hit(_) => MoveOutcome::Hit,
+            BoardChange::Sink { .. } => MoveOutcome::Sink,
+        }
+    }
+}
+
+impl Board {
+    /// Lists the shots that turned this board into `next`.
+    pub fn diff(&self, next: &Board) -> Vec<BoardChange> {
+        let changed_fields = self.get_changed_fields(next);
+        let mut changes = Vec::new();
+
+        let (mut destroyed_fields, other_fields): (Vec<Position>, Vec<Position>) = changed_fields
+            .into_iter()
+            .partition(|field| next.check_field(*field, vec![FieldState::Destroyed]));
+
+        for field in other_fields {
+            match next.get_field(field) {
+                FieldState::Empty => changes.push(BoardChange::Miss(field)),
+                FieldState::Damaged => changes.push(BoardChange::Hit(field)),
+                _ => {}
+            }
+        }
+
+        // Every connected group of newly destroyed fields is one sunk ship
+        while let Some(first) = destroyed_fields.pop() {
+            let mut ship = vec![first];
+            let mut index = 0;
+
+            while index < ship.len() {
+                let field = ship[index];
+
+                let (neighbors, rest): (Vec<Position>, Vec<Position>) = destroyed_fields
+                    .into_iter()
+                    .partition(|other| field.get_neighbors().any(|neighbor| neighbor == *other));
+
+                ship.extend(neighbors);
+                destroyed_fields = rest;
+                index += 1;
+            }
+
+            // The fields hit before were damaged, the remaining one is the shot
+            let position = ship
+                .iter()
+                .find(|field| !self.check_field(**field, vec![FieldState::Damaged]))
+                .copied()
+                .unwrap_or(first);
+
+            changes.push(BoardChange::Sink {
+                position,
+                ship: ship
+                    .into_iter()
+                    .sorted_by_key(|field| (field.x, field.y))
+                    .collect(),
+            });
+        }
+
+        changes
+    }
+
+    /// Applies the outcome of a shot, the reverse of [`Board::diff`].
+    pub fn apply_shot(&mut self, position: Position, outcome: MoveOutcome) {
+        match outcome {
+            MoveOutcome::Miss => self.set_field(position, FieldState::Empty),
+            MoveOutcome::Hit => self.set_field(position, FieldState::Damaged),
+            MoveOutcome::Sink => {
+                let mut ship = vec![position];
+
+                // The damaged fields connected to the shot belong to the sunk ship
+                while let Some(field) = ship.pop() {
+                    self.set_field(field, FieldState::Destroyed);
+
+                    for neighbor in field.get_neighbors() {
+                        if self.check_field(neighbor, vec![FieldState::Damaged]) {
+                            ship.push(neighbor);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Change caused by a single shot at `position` between this board and
+    /// `next`.
+    pub fn get_change_at(&self, next: &Board, position: Position) -> Option<BoardChange> {
+        self.diff(next)
+            .into_iter()
+            .find(|change| change.get_position() == position)
+    }
+}
@@ -0,0 +1,139 @@
+use crate::network::models::{Board, FieldState, Placement, Position};
+
+#[derive(sqlx::Type, Debug, Clone, Copy, PartialEq, Eq)]
+#[sqlx(type_name = "desynckind", rename_all = "snake_case")]
+pub enum DesyncKind {
+    /// Field has a state that should never be visible on this board
+    UnexpectedState,
+    /// We shot at the field, but it is still unknown
+    MissingShot,
+    /// Field is revealed, but we never shot at it
+    UnexpectedShot,
+    /// Hit fields can only be explained by ships touching each other
+    TouchingShips,
+    /// Own board doesn't match the placement we submitted
+    PlacementMismatch,
+}
+
+#[derive(Debug, Clone)]
+pub struct Desync {
+    pub kind: DesyncKind,
+    pub position: Position,
+    pub details: String,
+}
+
+const SHIP_STATES: [FieldState; 3] = [FieldState::Ship, FieldState::Damaged, FieldState::Destroyed];
+const HIT_STATES: [FieldState; 2] = [FieldState::Damaged, FieldState::Destroyed];
+
+impl Board {
+    /// Checks the opponent board against the shots we fired.
+    pub fn check_shots(&self, shots: &[Position]) -> Vec<Desync> {
+        let mut desyncs = Vec::new();
+
+        for position in self.find_fields(vec![FieldState::Ship], |_| true) {
+            desyncs.push(Desync {
+                kind: DesyncKind::UnexpectedState,
+                position,
+                details: String::from("Intact ship field on opponent board"),
+            });
+        }
+
+        for position in shots {
+            if self.check_field(*position, vec![FieldState::Unknown]) {
+                desyncs.push(Desync {
+                    kind: DesyncKind::MissingShot,
+                    position: *position,
+                    details: String::from("Shot field is still unknown"),
+                });
+            }
+        }
+
+        let revealed_fields = self.find_fields(
+            vec![
+                FieldState::Empty,
+                FieldState::Damaged,
+                FieldState::Destroyed,
+            ],
+            |position| !shots.contains(position),
+        );
+
+        for position in revealed_fields {
+            desyncs.push(Desync {
+                kind: DesyncKind::UnexpectedShot,
+                position,
+                details: format!("Field is {:?} without a shot", self.get_field(position)),
+            });
+        }
+
+        desyncs.extend(self.check_touching_ships());
+
+        desyncs
+    }
+
+    /// Checks our own board against the placement we submitted.
+    pub fn check_placement(&self, placement: &Placement) -> Vec<Desync> {
+        let mut desyncs = Vec::new();
+
+        for (x, col) in self.fields.iter().enumerate() {
+            for (y, field) in col.iter().enumerate() {
+                let position = Position {
+                    x: x as i8,
+                    y: y as i8,
+                };
+
+                let expected = placement
+                    .ships
+                    .iter()
+                    .any(|ship| ship.get_occupied_fields().contains(&position));
+
+                if expected != SHIP_STATES.contains(field) {
+                    desyncs.push(Desync {
+                        kind: DesyncKind::PlacementMismatch,
+                        position,
+                        details: format!(
+                            "Field is {:?}, but {} placed a ship there",
+                            field,
+                            if expected { "we" } else { "we never" }
+                        ),
+                    });
+                }
+            }
+        }
+
+        desyncs
+    }
+
+    fn check_touching_ships(&self) -> Vec<Desync> {
+        let mut desyncs = Vec::new();
+
+        for position in self.find_fields(HIT_STATES.to_vec(), |_| true) {
+            let state = self.get_field(position);
+
+            let is_hit =
+                |x: i8, y: i8| self.check_field(position.offset(x, y), HIT_STATES.to_vec());
+
+            // Ships are straight lines, so diagonal hits belong to another ship.
+            // Only look forward to report every pair once.
+            let diagonal = is_hit(1, 1) || is_hit(-1, 1);
+
+            // A destroyed ship is complete, so a bend or a damaged neighbor
+            // means a second ship is touching it
+            let bent = (is_hit(1, 0) || is_hit(-1, 0)) && (is_hit(0, 1) || is_hit(0, -1));
+
+            let damaged_neighbor = state == FieldState::Destroyed
+                && position
+                    .get_neighbors()
+                    .any(|neighbor| self.check_field(neighbor, vec![FieldState::Damaged]));
+
+            if diagonal || bent || damaged_neighbor {
+                desyncs.push(Desync {
+                    kind: DesyncKind::TouchingShips,
+                    position,
+                    details: String::from("Hit field touches another ship"),
+                });
+            }
+        }
+
+        desyncs
+    }
+}
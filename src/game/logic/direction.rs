@@ -11,3 +11,13 @@ impl Distribution<Direction> for Standard {
         }
     }
 }
+
+impl Direction {
+    /// Offset between two consecutive fields of a ship
+    pub fn get_step(&self) -> (i8, i8) {
+        match self {
+            Direction::Horizontal => (1, 0),
+            Direction::Vertical => (0, 1),
+        }
+    }
+}
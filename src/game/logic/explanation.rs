@@ -0,0 +1,197 @@
+use std::{fmt, time::Duration};
+
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use crate::network::models::Position;
+
+use super::{heatmap::Heatmap, strategy::AttackStrategy};
+
+/// Candidates kept in an explanation, the hottest ones
+const MAX_CANDIDATES: usize = 10;
+/// Candidates shown in the log line
+const LOGGED_CANDIDATES: usize = 3;
+
+/// Hunt/target stages, in the order they are tried.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HuntStage {
+    /// Continues a line of damaged fields
+    Line,
+    /// Surrounds a single damaged field
+    Neighbors,
+    /// Searches a checkerboard pattern for new ships
+    Checkerboard,
+}
+
+/// How a strategy picks among its candidates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TieBreak {
+    /// Hottest candidate, the first one in board order on ties
+    HottestFirst,
+    /// Hottest candidate, the last one in board order on ties
+    HottestLast,
+    /// First candidate, heat is ignored
+    FirstCandidate,
+    /// Random candidate, heat is ignored
+    Random,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Candidate {
+    pub position: Position,
+    pub heat: u32,
+    /// Share of the heat of all candidates
+    pub probability: f64,
+}
+
+/// Why a strategy chose an attack, stored with the round.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MoveExplanation {
+    pub strategy: AttackStrategy,
+    /// Stage that produced the candidates, only for hunt/target strategies
+    pub stage: Option<HuntStage>,
+    pub position: Option<Position>,
+    pub candidate_count: usize,
+    /// Hottest candidates first, the chosen one is always included
+    pub candidates: Vec<Candidate>,
+    pub tie_break: TieBreak,
+    /// Candidates that were as good as the chosen one under the tie-break
+    pub ties: usize,
+    /// Time spent on the move, including the heatmap
+    pub duration_ms: u64,
+}
+
+impl MoveExplanation {
+    pub fn new(
+        strategy: AttackStrategy,
+        stage: Option<HuntStage>,
+        candidates: &[Position],
+        heatmap: &Heatmap,
+        tie_break: TieBreak,
+        position: Option<Position>,
+    ) -> MoveExplanation {
+        let get_heat =
+            |position: Position| heatmap.fields[position.x as usize][position.y as usize];
+
+        let total_heat: u32 = candidates.iter().map(|&position| get_heat(position)).sum();
+        let to_candidate = |position: Position| Candidate {
+            position,
+            heat: get_heat(position),
+            probability: match total_heat {
+                0 => 0.0,
+                total => get_heat(position) as f64 / total as f64,
+            },
+        };
+
+        let mut kept = candidates
+            .iter()
+            .copied()
+            .sorted_by(|&a, &b| get_heat(b).cmp(&get_heat(a)))
+            .take(MAX_CANDIDATES)
+            .collect_vec();
+
+        if let Some(position) = position {
+            if !kept.contains(&position) {
+                kept.push(position);
+            }
+        }
+
+        let ties = match (tie_break, position) {
+            (_, None) => 0,
+            (TieBreak::HottestFirst | TieBreak::HottestLast, Some(position)) => candidates
+                .iter()
+                .filter(|&&candidate| get_heat(candidate) == get_heat(position))
+                .count(),
+            (TieBreak::FirstCandidate, Some(_)) => 1,
+            (TieBreak::Random, Some(_)) => candidates.len(),
+        };
+
+        MoveExplanation {
+            strategy,
+            stage,
+            position,
+            candidate_count: candidates.len(),
+            candidates: kept.into_iter().map(to_candidate).collect(),
+            tie_break,
+            ties,
+            duration_ms: 0,
+        }
+    }
+
+    pub fn set_duration(&mut self, duration: Duration) {
+        self.duration_ms = duration.as_millis() as u64;
+    }
+}
+
+impl HuntStage {
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            HuntStage::Line => "line",
+            HuntStage::Neighbors => "neighbors",
+            HuntStage::Checkerboard => "checkerboard",
+        }
+    }
+}
+
+impl TieBreak {
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            TieBreak::HottestFirst => "hottest_first",
+            TieBreak::HottestLast => "hottest_last",
+            TieBreak::FirstCandidate => "first_candidate",
+            TieBreak::Random => "random",
+        }
+    }
+}
+
+impl fmt::Display for Candidate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} heat {} ({:.1}%)",
+            self.position,
+            self.heat,
+            self.probability * 100.0
+        )
+    }
+}
+
+/// One line summary, the candidates are cut to the hottest few.
+impl fmt::Display for MoveExplanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.strategy)?;
+
+        if let Some(stage) = self.stage {
+            write!(f, "/{}", stage.get_name())?;
+        }
+
+        match self.position {
+            Some(position) => write!(f, " chose {}", position)?,
+            None => write!(f, " chose nothing")?,
+        }
+
+        write!(
+            f,
+            " from {} candidates by {} ({} tied) in {}ms",
+            self.candidate_count,
+            self.tie_break.get_name(),
+            self.ties,
+            self.duration_ms
+        )?;
+
+        if !self.candidates.is_empty() {
+            let candidates = self
+                .candidates
+                .iter()
+                .take(LOGGED_CANDIDATES)
+                .map(Candidate::to_string)
+                .join(", ");
+
+            write!(f, ": {}", candidates)?;
+        }
+
+        Ok(())
+    }
+}
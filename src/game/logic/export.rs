@@ -0,0 +1,277 @@
+use std::fmt::Write;
+
+use anyhow::Result;
+
+use crate::network::models::{Board, FieldState, Position};
+
+use super::{
+    grid::{Rect, BOARD_SIZE, COLUMN_LABELS},
+    heatmap::Heatmap,
+};
+
+const CELL_SIZE: u32 = 32;
+/// Space for the row and column labels
+const MARGIN: u32 = 24;
+const SIZE: u32 = MARGIN + BOARD_SIZE as u32 * CELL_SIZE;
+
+type Color = (u8, u8, u8);
+
+const BACKGROUND: Color = (255, 255, 255);
+const GRID: Color = (160, 160, 160);
+const UNKNOWN: Color = (225, 230, 235);
+const EMPTY: Color = (250, 250, 250);
+const DAMAGED: Color = (240, 140, 40);
+const DESTROYED: Color = (150, 20, 20);
+const SHIP: Color = (110, 110, 120);
+const COLD: Color = (40, 70, 170);
+const HOT: Color = (230, 40, 40);
+const HIGHLIGHT: Color = (20, 200, 60);
+
+/// Renders a board as SVG or PNG image, with the same options as
+/// [`BoardRenderer`](super::render::BoardRenderer).
+pub struct BoardImage<'a> {
+    board: &'a Board,
+    heatmap: Option<&'a Heatmap>,
+    revealed_board: Option<&'a Board>,
+    shot: Option<Position>,
+}
+
+impl<'a> BoardImage<'a> {
+    pub fn new(board: &'a Board) -> BoardImage<'a> {
+        BoardImage {
+            board,
+            heatmap: None,
+            revealed_board: None,
+            shot: None,
+        }
+    }
+
+    pub fn heatmap(mut self, heatmap: &'a Heatmap) -> BoardImage<'a> {
+        self.heatmap = Some(heatmap);
+        self
+    }
+
+    /// Outlines the ships of the final board sent with the game result.
+    pub fn revealed_board(mut self, board: &'a Board) -> BoardImage<'a> {
+        self.revealed_board = Some(board);
+        self
+    }
+
+    pub fn shot(mut self, position: Position) -> BoardImage<'a> {
+        self.shot = Some(position);
+        self
+    }
+
+    pub fn to_svg(&self) -> String {
+        let mut svg = String::new();
+
+        writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{size}" height="{size}" viewBox="0 0 {size} {size}" font-family="monospace" font-size="14">"#,
+            size = SIZE
+        )
+        .unwrap();
+        writeln!(
+            svg,
+            r#"<rect width="{size}" height="{size}" fill="{}"/>"#,
+            to_hex(BACKGROUND),
+            size = SIZE
+        )
+        .unwrap();
+
+        for (index, label) in (0..).zip(COLUMN_LABELS.chars()) {
+            let center = MARGIN + index * CELL_SIZE + CELL_SIZE / 2;
+
+            writeln!(
+                svg,
+                r#"<text x="{}" y="{}" text-anchor="middle" dominant-baseline="central">{}</text>"#,
+                center,
+                MARGIN / 2,
+                label
+            )
+            .unwrap();
+            writeln!(
+                svg,
+                r#"<text x="{}" y="{}" text-anchor="middle" dominant-baseline="central">{}</text>"#,
+                MARGIN / 2,
+                center,
+                index + 1
+            )
+            .unwrap();
+        }
+
+        for (position, color) in self.get_cells() {
+            let (left, top) = get_origin(position);
+            let state = self.board.get_field(position);
+
+            writeln!(
+                svg,
+                r#"<rect x="{}" y="{}" width="{size}" height="{size}" fill="{}" stroke="{}"/>"#,
+                left,
+                top,
+                to_hex(color),
+                to_hex(GRID),
+                size = CELL_SIZE
+            )
+            .unwrap();
+
+            if matches!(state, FieldState::Damaged | FieldState::Destroyed) {
+                writeln!(
+                    svg,
+                    r#"<text x="{}" y="{}" text-anchor="middle" dominant-baseline="central" fill="white">{}</text>"#,
+                    left + CELL_SIZE / 2,
+                    top + CELL_SIZE / 2,
+                    state.get_symbol()
+                )
+                .unwrap();
+            }
+        }
+
+        for position in self.get_revealed_fields() {
+            let (left, top) = get_origin(position);
+
+            writeln!(
+                svg,
+                r#"<rect x="{}" y="{}" width="{size}" height="{size}" fill="none" stroke="{}" stroke-width="3"/>"#,
+                left + 3,
+                top + 3,
+                to_hex(SHIP),
+                size = CELL_SIZE - 6
+            )
+            .unwrap();
+        }
+
+        if let Some(position) = self.shot {
+            let (left, top) = get_origin(position);
+
+            writeln!(
+                svg,
+                r#"<rect x="{}" y="{}" width="{size}" height="{size}" fill="none" stroke="{}" stroke-width="4"/>"#,
+                left,
+                top,
+                to_hex(HIGHLIGHT),
+                size = CELL_SIZE
+            )
+            .unwrap();
+        }
+
+        svg.push_str("</svg>\n");
+
+        svg
+    }
+
+    /// Encodes the image as PNG. Unlike the SVG it has no labels or symbols.
+    pub fn to_png(&self) -> Result<Vec<u8>> {
+        let mut pixels = vec![BACKGROUND; (SIZE * SIZE) as usize];
+
+        let mut fill =
+            |left: u32, top: u32, size: u32, border: u32, color: Color, outline: bool| {
+                for y in top..top + size {
+                    for x in left..left + size {
+                        let is_border = x < left + border
+                            || x >= left + size - border
+                            || y < top + border
+                            || y >= top + size - border;
+
+                        if !outline || is_border {
+                            pixels[(y * SIZE + x) as usize] = color;
+                        }
+                    }
+                }
+            };
+
+        for (position, color) in self.get_cells() {
+            let (left, top) = get_origin(position);
+
+            fill(left, top, CELL_SIZE, 1, GRID, false);
+            fill(left + 1, top + 1, CELL_SIZE - 2, 0, color, false);
+        }
+
+        for position in self.get_revealed_fields() {
+            let (left, top) = get_origin(position);
+
+            fill(left + 3, top + 3, CELL_SIZE - 6, 3, SHIP, true);
+        }
+
+        if let Some(position) = self.shot {
+            let (left, top) = get_origin(position);
+
+            fill(left, top, CELL_SIZE, 4, HIGHLIGHT, true);
+        }
+
+        let mut png = Vec::new();
+        let mut encoder = png::Encoder::new(&mut png, SIZE, SIZE);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let data = pixels
+            .into_iter()
+            .flat_map(|(r, g, b)| [r, g, b])
+            .collect::<Vec<_>>();
+
+        encoder.write_header()?.write_image_data(&data)?;
+
+        Ok(png)
+    }
+
+    fn get_cells(&self) -> Vec<(Position, Color)> {
+        let max_heat = self
+            .heatmap
+            .and_then(|heatmap| heatmap.fields.iter().flatten().max().copied())
+            .unwrap_or(0)
+            .max(1);
+
+        let mut cells = Vec::new();
+
+        for position in Rect::board().iter() {
+            let color = match (self.board.get_field(position), self.heatmap) {
+                (FieldState::Unknown, Some(heatmap)) => mix(
+                    COLD,
+                    HOT,
+                    heatmap.fields[position.x as usize][position.y as usize] as f32
+                        / max_heat as f32,
+                ),
+                (FieldState::Unknown, None) => UNKNOWN,
+                (FieldState::Empty, _) => EMPTY,
+                (FieldState::Damaged, _) => DAMAGED,
+                (FieldState::Destroyed, _) => DESTROYED,
+                (FieldState::Ship, _) => SHIP,
+            };
+
+            cells.push((position, color));
+        }
+
+        cells
+    }
+
+    fn get_revealed_fields(&self) -> Vec<Position> {
+        match self.revealed_board {
+            Some(board) => board.find_fields(
+                vec![FieldState::Ship, FieldState::Damaged, FieldState::Destroyed],
+                |_| true,
+            ),
+            None => Vec::new(),
+        }
+    }
+}
+
+fn get_origin(position: Position) -> (u32, u32) {
+    (
+        MARGIN + position.x as u32 * CELL_SIZE,
+        MARGIN + position.y as u32 * CELL_SIZE,
+    )
+}
+
+fn mix(from: Color, to: Color, ratio: f32) -> Color {
+    let channel = |from: u8, to: u8| (from as f32 + (to as f32 - from as f32) * ratio) as u8;
+
+    (
+        channel(from.0, to.0),
+        channel(from.1, to.1),
+        channel(from.2, to.2),
+    )
+}
+
+fn to_hex((r, g, b): Color) -> String {
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
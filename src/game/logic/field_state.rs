@@ -0,0 +1,26 @@
+use crate::network::models::FieldState;
+
+impl FieldState {
+    /// Single character used when rendering boards. Unknown fields are `~`,
+    /// the other symbols match the server format.
+    pub fn get_symbol(&self) -> char {
+        match self {
+            FieldState::Damaged => 'x',
+            FieldState::Destroyed => 'X',
+            FieldState::Empty => '.',
+            FieldState::Ship => 'O',
+            FieldState::Unknown => '~',
+        }
+    }
+
+    pub fn from_symbol(symbol: char) -> Option<FieldState> {
+        match symbol {
+            'x' => Some(FieldState::Damaged),
+            'X' => Some(FieldState::Destroyed),
+            '.' => Some(FieldState::Empty),
+            'O' => Some(FieldState::Ship),
+            '~' => Some(FieldState::Unknown),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,411 @@
+use rand::{seq::SliceRandom, Rng};
+
+use crate::network::models::{Board, Direction, FieldState, Placement, Position, Ship};
+
+/// Ship sizes every player has to place
+pub const FLEET: [i8; 5] = [5, 4, 3, 3, 2];
+
+const SHIP_STATES: [FieldState; 3] = [FieldState::Ship, FieldState::Damaged, FieldState::Destroyed];
+
+/// Layouts `sample_fleet` draws before giving up
+const MAX_SAMPLE_ATTEMPTS: usize = 10_000;
+
+/// Draws a placement uniformly from all legal placements of the fleet that
+/// agree with the board: no ship covers an empty field and every field with
+/// a (hit) ship is covered.
+///
+/// Whole layouts are drawn and rejected until one is legal. Drawing ships one
+/// by one and retrying only the failed ship would favor layouts where the
+/// large ships leave a lot of room. Heavily constrained boards are rejected
+/// often, so this gives up with `None` after `MAX_SAMPLE_ATTEMPTS`, the same
+/// as if there was no legal placement. Enumerate those with
+/// [`FleetPlacements`] once they are small enough.
+pub fn sample_fleet<R: Rng + ?Sized>(
+    rng: &mut R,
+    board: &Board,
+    sizes: &[i8],
+) -> Option<Placement> {
+    let candidates = sizes
+        .iter()
+        .map(|&size| get_ship_candidates(board, size))
+        .collect::<Vec<_>>();
+
+    if candidates.iter().any(Vec::is_empty) {
+        return None;
+    }
+
+    let required = board.find_fields(SHIP_STATES.to_vec(), |_| true);
+
+    (0..MAX_SAMPLE_ATTEMPTS).find_map(|_| draw_layout(rng, &candidates, &required))
+}
+
+/// Draws a placement of the whole fleet uniformly, see [`sample_fleet`]. On
+/// an empty board a good share of the layouts is legal, so this never needs
+/// to give up.
+pub fn sample_free_fleet<R: Rng + ?Sized>(rng: &mut R) -> Placement {
+    let board = Board::new();
+    let candidates = FLEET
+        .iter()
+        .map(|&size| get_ship_candidates(&board, size))
+        .collect::<Vec<_>>();
+
+    loop {
+        if let Some(placement) = draw_layout(rng, &candidates, &[]) {
+            return placement;
+        }
+    }
+}
+
+/// Draws one candidate per ship, `None` if they collide or leave a required
+/// field uncovered.
+fn draw_layout<R: Rng + ?Sized>(
+    rng: &mut R,
+    candidates: &[Vec<Ship>],
+    required: &[Position],
+) -> Option<Placement> {
+    let mut placement = Placement::new();
+
+    for ships in candidates {
+        let ship = *ships.choose(rng)?;
+
+        if placement
+            .ships
+            .iter()
+            .any(|other| other.collides_with(ship.get_rect()))
+        {
+            return None;
+        }
+
+        placement.ships.push(ship);
+    }
+
+    Some(placement).filter(|placement| covers_all(placement, required))
+}
+
+/// Every ship of the given size that fits on the board without covering an
+/// empty field.
+fn get_ship_candidates(board: &Board, size: i8) -> Vec<Ship> {
+    let bounds = board.get_rect();
+
+    bounds
+        .iter()
+        .flat_map(|start| {
+            [Direction::Horizontal, Direction::Vertical].map(|direction| Ship {
+                start,
+                direction,
+                size,
+            })
+        })
+        .filter(|ship| bounds.contains_rect(&ship.get_rect()))
+        .filter(|ship| {
+            ship.get_rect()
+                .iter()
+                .all(|field| board.get_field(field) != FieldState::Empty)
+        })
+        .collect()
+}
+
+fn covers_all(placement: &Placement, fields: &[Position]) -> bool {
+    fields.iter().all(|&field| {
+        placement
+            .ships
+            .iter()
+            .any(|ship| ship.get_rect().contains(field))
+    })
+}
+
+/// Iterates over all legal placements of a fleet that agree with a board, see
+/// [`sample_fleet`]. Each layout is yielded once, even if the fleet contains
+/// several ships of the same size.
+///
+/// The search is exhaustive, so this is only feasible once a good part of the
+/// board is revealed.
+pub struct FleetPlacements {
+    sizes: Vec<i8>,
+    candidates: Vec<Vec<Ship>>,
+    required: Vec<Position>,
+    /// Next candidate to try for every ship that is currently being placed
+    cursors: Vec<usize>,
+    placement: Placement,
+    /// Whether an empty fleet still has to yield its only placement
+    empty_pending: bool,
+}
+
+impl FleetPlacements {
+    pub fn new(board: &Board, sizes: &[i8]) -> FleetPlacements {
+        let mut sizes = sizes.to_vec();
+
+        // Ships of equal size have to be next to each other to skip duplicates
+        sizes.sort_unstable_by(|a, b| b.cmp(a));
+
+        let cursors = if sizes.is_empty() {
+            Vec::new()
+        } else {
+            vec![0]
+        };
+
+        let required = board.find_fields(SHIP_STATES.to_vec(), |_| true);
+
+        FleetPlacements {
+            candidates: sizes
+                .iter()
+                .map(|&size| get_ship_candidates(board, size))
+                .collect(),
+            empty_pending: sizes.is_empty() && required.is_empty(),
+            sizes,
+            required,
+            cursors,
+            placement: Placement::new(),
+        }
+    }
+
+    fn can_place(&self, ship: &Ship) -> bool {
+        let rect = ship.get_rect();
+
+        if self
+            .placement
+            .ships
+            .iter()
+            .any(|other| other.collides_with(rect))
+        {
+            return false;
+        }
+
+        // Ship fields next to the ship can't be covered by any other ship
+        if self
+            .required
+            .iter()
+            .any(|&field| rect.expand(1).contains(field) && !rect.contains(field))
+        {
+            return false;
+        }
+
+        let uncovered = self
+            .required
+            .iter()
+            .filter(|&&field| {
+                !rect.contains(field)
+                    && !self
+                        .placement
+                        .ships
+                        .iter()
+                        .any(|other| other.get_rect().contains(field))
+            })
+            .count();
+
+        let remaining_size: i8 = self.sizes[self.placement.ships.len() + 1..].iter().sum();
+
+        uncovered <= remaining_size as usize
+    }
+}
+
+impl Iterator for FleetPlacements {
+    type Item = Placement;
+
+    fn next(&mut self) -> Option<Placement> {
+        if std::mem::take(&mut self.empty_pending) {
+            return Some(Placement::new());
+        }
+
+        loop {
+            let depth = self.cursors.len().checked_sub(1)?;
+            let index = self.cursors[depth];
+
+            if index >= self.candidates[depth].len() {
+                self.cursors.pop();
+                self.placement.ships.pop();
+                continue;
+            }
+
+            self.cursors[depth] += 1;
+
+            let ship = self.candidates[depth][index];
+
+            if !self.can_place(&ship) {
+                continue;
+            }
+
+            // The last ship can only be placed if it covers the remaining hits
+            if depth + 1 == self.sizes.len() {
+                let mut placement = self.placement.clone();
+                placement.ships.push(ship);
+
+                return Some(placement);
+            }
+
+            self.placement.ships.push(ship);
+
+            // Ships of equal size share their candidates, only take later ones
+            let start = if self.sizes[depth + 1] == self.sizes[depth] {
+                index + 1
+            } else {
+                0
+            };
+
+            self.cursors.push(start);
+        }
+    }
+}
+
+impl Placement {
+    /// All legal placements of the fleet that agree with the board.
+    pub fn get_all(board: &Board, sizes: &[i8]) -> FleetPlacements {
+        FleetPlacements::new(board, sizes)
+    }
+
+    pub fn count_all(board: &Board, sizes: &[i8]) -> usize {
+        FleetPlacements::new(board, sizes).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use itertools::Itertools;
+
+    use super::*;
+
+    fn small_board(width: usize, height: usize) -> Board {
+        Board {
+            fields: vec![vec![FieldState::Unknown; height]; width],
+        }
+    }
+
+    fn is_legal(placement: &Placement, board: &Board, sizes: &[i8]) -> bool {
+        let mut placed = placement
+            .ships
+            .iter()
+            .map(|ship| ship.size)
+            .collect::<Vec<_>>();
+        let mut expected = sizes.to_vec();
+        placed.sort_unstable();
+        expected.sort_unstable();
+
+        let fits = placement.ships.iter().all(|ship| {
+            board.get_rect().contains_rect(&ship.get_rect())
+                && ship
+                    .get_rect()
+                    .iter()
+                    .all(|field| board.get_field(field) != FieldState::Empty)
+        });
+
+        let apart = placement
+            .ships
+            .iter()
+            .tuple_combinations()
+            .all(|(a, b)| !a.collides_with(b.get_rect()));
+
+        let required = board.find_fields(SHIP_STATES.to_vec(), |_| true);
+
+        placed == expected && fits && apart && covers_all(placement, &required)
+    }
+
+    /// Tries every combination of candidates, counting layouts that only
+    /// differ in the order of equal ships once
+    fn count_brute_force(board: &Board, sizes: &[i8]) -> usize {
+        sizes
+            .iter()
+            .map(|&size| get_ship_candidates(board, size))
+            .multi_cartesian_product()
+            .map(|ships| Placement { ships })
+            .filter(|placement| is_legal(placement, board, sizes))
+            .map(|placement| {
+                placement
+                    .ships
+                    .into_iter()
+                    .sorted_by_key(|ship| {
+                        (
+                            ship.start.x,
+                            ship.start.y,
+                            ship.size,
+                            ship.direction == Direction::Vertical,
+                        )
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<HashSet<_>>()
+            .len()
+    }
+
+    fn constrained_board() -> Board {
+        let mut board = small_board(5, 5);
+        board.set_field(Position { x: 0, y: 0 }, FieldState::Empty);
+        board.set_field(Position { x: 2, y: 2 }, FieldState::Empty);
+        board.set_field(Position { x: 1, y: 4 }, FieldState::Damaged);
+        board.set_field(Position { x: 4, y: 1 }, FieldState::Ship);
+        board
+    }
+
+    #[test]
+    fn count_all_matches_brute_force() {
+        let cases: [(Board, &[i8]); 5] = [
+            (small_board(4, 4), &[3, 2]),
+            (small_board(4, 4), &[2, 2]),
+            (small_board(5, 4), &[3, 2, 2]),
+            (constrained_board(), &[3, 2]),
+            (constrained_board(), &[2, 2, 1]),
+        ];
+
+        for (board, sizes) in cases {
+            assert_eq!(
+                Placement::count_all(&board, sizes),
+                count_brute_force(&board, sizes),
+                "sizes {:?} on {:?}",
+                sizes,
+                board
+            );
+        }
+    }
+
+    #[test]
+    fn get_all_yields_legal_layouts() {
+        let board = constrained_board();
+
+        for placement in Placement::get_all(&board, &[3, 2]) {
+            assert!(is_legal(&placement, &board, &[3, 2]), "{:?}", placement);
+        }
+    }
+
+    #[test]
+    fn empty_fleet_has_one_layout() {
+        assert_eq!(Placement::count_all(&small_board(3, 3), &[]), 1);
+        assert_eq!(Placement::count_all(&constrained_board(), &[]), 0);
+    }
+
+    #[test]
+    fn sample_fleet_returns_legal_layouts() {
+        let mut rng = rand::thread_rng();
+        let board = Board::new();
+
+        for _ in 0..100 {
+            let placement = sample_fleet(&mut rng, &board, &FLEET).unwrap();
+
+            assert!(placement.validate().is_empty(), "{:?}", placement);
+            assert!(is_legal(&placement, &board, &FLEET), "{:?}", placement);
+
+            let placement = sample_free_fleet(&mut rng);
+
+            assert!(placement.validate().is_empty(), "{:?}", placement);
+            assert!(is_legal(&placement, &board, &FLEET), "{:?}", placement);
+        }
+
+        let board = constrained_board();
+
+        for _ in 0..100 {
+            let placement = sample_fleet(&mut rng, &board, &[3, 2]).unwrap();
+
+            assert!(is_legal(&placement, &board, &[3, 2]), "{:?}", placement);
+        }
+    }
+
+    #[test]
+    fn sample_fleet_without_layout_returns_none() {
+        let mut rng = rand::thread_rng();
+
+        assert_eq!(sample_fleet(&mut rng, &small_board(4, 4), &[5]), None);
+
+        // Both ships fit, but not apart from each other
+        assert_eq!(sample_fleet(&mut rng, &small_board(3, 2), &[3, 3]), None);
+    }
+}
@@ -2,30 +2,39 @@ use crate::network::models::{GameDetails, Player};
 
 impl GameDetails {
     pub fn get_self(&self) -> &Player {
-        self.players
-            .iter()
-            .find(|player| player.id == self.self_id)
-            .unwrap()
+        self.find_self().unwrap()
     }
 
     pub fn get_opponent(&self) -> &Player {
-        self.players
-            .iter()
-            .find(|player| player.id != self.self_id)
-            .unwrap()
+        self.find_opponent().unwrap()
     }
 
     pub fn get_self_number(&self) -> usize {
+        self.find_self_number().unwrap()
+    }
+
+    pub fn get_opponent_number(&self) -> usize {
+        self.find_opponent_number().unwrap()
+    }
+
+    /// `None` if no player has our id, e.g. in an event the server got wrong
+    pub fn find_self(&self) -> Option<&Player> {
+        self.players.iter().find(|player| player.id == self.self_id)
+    }
+
+    pub fn find_opponent(&self) -> Option<&Player> {
+        self.players.iter().find(|player| player.id != self.self_id)
+    }
+
+    pub fn find_self_number(&self) -> Option<usize> {
         self.players
             .iter()
             .position(|player| player.id == self.self_id)
-            .unwrap()
     }
 
-    pub fn get_opponent_number(&self) -> usize {
+    pub fn find_opponent_number(&self) -> Option<usize> {
         self.players
             .iter()
             .position(|player| player.id != self.self_id)
-            .unwrap()
     }
 }
@@ -1,4 +1,18 @@
-use crate::network::models::{Board, BoardOrBool, GameResultEvent, GameRoundEvent};
+use crate::network::models::{
+    Board, BoardOrBool, FieldState, GameDetails, GameEvent, GameResultEvent, GameRoundEvent,
+};
+
+impl GameEvent {
+    pub fn get_details(&self) -> Option<&GameDetails> {
+        match self {
+            GameEvent::Init(event) => Some(&event.details),
+            GameEvent::Result(event) => Some(&event.details),
+            GameEvent::Round(event) => Some(&event.details),
+            GameEvent::Set(event) => Some(&event.details),
+            GameEvent::Unknown(event) => event.details.as_ref(),
+        }
+    }
+}
 
 impl GameRoundEvent {
     pub fn get_self_board(&self) -> &Board {
@@ -12,6 +26,21 @@ impl GameRoundEvent {
 
         &self.boards[opponent_number]
     }
+
+    /// Like `get_opponent_board`, but never panics. Without our player in the
+    /// event, the opponent board is any board still having unknown fields.
+    pub fn find_opponent_board(&self) -> Option<&Board> {
+        match self.details.find_self_number() {
+            Some(self_number) => self.boards.get(1 - self_number),
+            None => self.boards.iter().find(|board| {
+                board
+                    .fields
+                    .iter()
+                    .flatten()
+                    .any(|&field| field == FieldState::Unknown)
+            }),
+        }
+    }
 }
 
 impl GameResultEvent {
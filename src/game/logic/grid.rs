@@ -0,0 +1,125 @@
+use crate::network::models::{Direction, Position};
+
+/// Width and height of the standard board
+pub const BOARD_SIZE: i8 = 10;
+
+/// Labels of the columns of the standard board, one letter per column
+pub const COLUMN_LABELS: &str = "ABCDEFGHIJ";
+
+const NEIGHBOR_STEPS: [(i8, i8); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+const SURROUNDING_STEPS: [(i8, i8); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// Rectangle of positions, `start` and `end` are both included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rect {
+    pub start: Position,
+    pub end: Position,
+}
+
+impl Rect {
+    pub fn new(start: Position, end: Position) -> Rect {
+        Rect { start, end }
+    }
+
+    /// All fields of a board with the given width and height
+    pub fn with_size(width: i8, height: i8) -> Rect {
+        Rect {
+            start: Position { x: 0, y: 0 },
+            end: Position {
+                x: width - 1,
+                y: height - 1,
+            },
+        }
+    }
+
+    /// All fields of the standard board
+    pub fn board() -> Rect {
+        Rect::with_size(BOARD_SIZE, BOARD_SIZE)
+    }
+
+    pub fn contains(&self, position: Position) -> bool {
+        (self.start.x..=self.end.x).contains(&position.x)
+            && (self.start.y..=self.end.y).contains(&position.y)
+    }
+
+    pub fn contains_rect(&self, other: &Rect) -> bool {
+        self.contains(other.start) && self.contains(other.end)
+    }
+
+    pub fn intersects(&self, other: &Rect) -> bool {
+        self.start.x <= other.end.x
+            && self.end.x >= other.start.x
+            && self.start.y <= other.end.y
+            && self.end.y >= other.start.y
+    }
+
+    /// Grows the rectangle by `margin` fields on every side.
+    pub fn expand(&self, margin: i8) -> Rect {
+        Rect {
+            start: self.start.offset(-margin, -margin),
+            end: self.end.offset(margin, margin),
+        }
+    }
+
+    /// Iterates column by column, matching the board's `fields[x][y]` layout.
+    pub fn iter(&self) -> impl Iterator<Item = Position> {
+        let Rect { start, end } = *self;
+
+        (start.x..=end.x).flat_map(move |x| (start.y..=end.y).map(move |y| Position { x, y }))
+    }
+}
+
+impl From<Position> for Rect {
+    fn from(position: Position) -> Self {
+        Rect::new(position, position)
+    }
+}
+
+impl Position {
+    pub fn offset(&self, x: i8, y: i8) -> Position {
+        Position {
+            x: self.x + x,
+            y: self.y + y,
+        }
+    }
+
+    /// Moves the position along the direction, negative distances move back.
+    pub fn moved(&self, direction: Direction, distance: i8) -> Position {
+        let (x, y) = direction.get_step();
+
+        self.offset(x * distance, y * distance)
+    }
+
+    pub fn is_on_board(&self) -> bool {
+        Rect::board().contains(*self)
+    }
+
+    /// The 4-connected neighbors, which may lie outside of the board.
+    pub fn get_neighbors(self) -> impl Iterator<Item = Position> {
+        NEIGHBOR_STEPS
+            .into_iter()
+            .map(move |(x, y)| self.offset(x, y))
+    }
+
+    /// The 8-connected neighbors, which may lie outside of the board.
+    pub fn get_surrounding(self) -> impl Iterator<Item = Position> {
+        SURROUNDING_STEPS
+            .into_iter()
+            .map(move |(x, y)| self.offset(x, y))
+    }
+
+    /// Walks in a straight line, starting with the position after this one.
+    /// The line is endless, callers have to stop it.
+    pub fn walk(self, x: i8, y: i8) -> impl Iterator<Item = Position> {
+        (1..).map(move |distance| self.offset(x * distance, y * distance))
+    }
+}
@@ -2,38 +2,30 @@ use std::collections::HashMap;
 
 use itertools::Itertools;
 use lazy_static::lazy_static;
+use log::error;
+use rayon::prelude::*;
 
-use crate::network::models::{Board, Direction, FieldState, Position, Ship};
+use crate::{
+    application::thread_pool::spawn_compute,
+    network::models::{Board, Direction, FieldState, Placement, Ship},
+};
+
+use super::grid::{Rect, BOARD_SIZE};
 
 lazy_static! {
     static ref SHIPS: Vec<PlacementShip> = {
         let mut ships: Vec<Ship> = Vec::new();
 
-        for x in 0..10 {
-            for y in 0..10 {
-                let start = Position { x, y };
-
-                for size in 2..=5 {
-                    for direction in [Direction::Horizontal, Direction::Vertical].into_iter() {
-                        match direction {
-                            Direction::Horizontal => {
-                                if x + size > 10 {
-                                    continue;
-                                }
-                            }
-                            Direction::Vertical => {
-                                if y + size > 10 {
-                                    continue;
-                                }
-                            }
-                        }
-
-                        let ship = Ship {
-                            start,
-                            size,
-                            direction,
-                        };
+        for start in Rect::board().iter() {
+            for size in 2..=5 {
+                for direction in [Direction::Horizontal, Direction::Vertical].into_iter() {
+                    let ship = Ship {
+                        start,
+                        size,
+                        direction,
+                    };
 
+                    if Rect::board().contains_rect(&ship.get_rect()) {
                         ships.push(ship);
                     }
                 }
@@ -48,62 +40,73 @@ lazy_static! {
     };
 }
 
+#[derive(Debug)]
 struct PlacementShip {
     id: Option<usize>,
     ship: Ship,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Heatmap {
     pub fields: Vec<Vec<u32>>,
 }
 
+/// Candidate ships of the previous round, which only shrink as the board gets
+/// revealed. Lets the next round skip filtering all possible ships again.
+#[derive(Debug)]
+pub struct IncrementalHeatmap {
+    board: Board,
+    candidates: Vec<&'static PlacementShip>,
+}
+
 impl Heatmap {
     fn get_possible_ships(board: &Board) -> Vec<&'static PlacementShip> {
-        let mut possible_ships: Vec<&PlacementShip> = Vec::new();
-
-        for ship in SHIPS.iter() {
-            let occupied_fields = ship.ship.get_occupied_fields();
-
-            if occupied_fields
-                .iter()
-                .any(|field| board.check_field(*field, vec![FieldState::Empty]))
-            {
-                continue;
-            }
-
-            possible_ships.push(ship);
-        }
-
-        possible_ships
+        SHIPS
+            .par_iter()
+            .filter(|ship| {
+                !ship
+                    .ship
+                    .get_occupied_fields()
+                    .iter()
+                    .any(|field| board.check_field(*field, vec![FieldState::Empty]))
+            })
+            .collect()
     }
 
-    fn get_ship_heat(board: &Board) -> HashMap<Ship, u32> {
+    fn get_placements(board: &Board, candidates: &[&PlacementShip]) -> Vec<Placement> {
         let mut placements = board.detect_placements();
 
         if placements.len() == 1 {
             let placement = placements.pop().unwrap();
-            let possible_ships = Self::get_possible_ships(board);
 
-            for placement_ship in possible_ships {
-                let mut new_placement = placement.clone();
+            placements = candidates
+                .par_iter()
+                .map(|placement_ship| {
+                    let mut new_placement = placement.clone();
 
-                new_placement.add_ship(placement_ship.ship).ok();
+                    new_placement.add_ship(placement_ship.ship).ok();
 
-                placements.push(new_placement);
-            }
+                    new_placement
+                })
+                .collect();
         }
 
         placements
-            .into_iter()
-            .map(|placement| placement.ships)
-            .fold(HashMap::new(), |mut acc, ships| {
-                for ship in ships {
-                    if let Some(heat) = acc.get_mut(&ship) {
-                        *heat += 1;
-                    } else {
-                        acc.insert(ship, 1);
-                    }
+    }
+
+    fn get_ship_heat(board: &Board, candidates: &[&PlacementShip]) -> HashMap<Ship, u32> {
+        Self::get_placements(board, candidates)
+            .into_par_iter()
+            .fold(HashMap::new, |mut acc, placement| {
+                for ship in placement.ships {
+                    *acc.entry(ship).or_insert(0) += 1;
+                }
+
+                acc
+            })
+            .reduce(HashMap::new, |mut acc, other| {
+                for (ship, heat) in other {
+                    *acc.entry(ship).or_insert(0) += heat;
                 }
 
                 acc
@@ -111,17 +114,115 @@ impl Heatmap {
     }
 
     pub fn from_board(board: &Board) -> Heatmap {
-        let fields = Self::get_ship_heat(board).into_iter().fold(
-            vec![vec![0; 10]; 10],
-            |mut acc, (ship, heat)| {
-                for field in ship.get_occupied_fields() {
-                    acc[field.x as usize][field.y as usize] += heat;
-                }
+        Self::from_candidates(board, &Self::get_possible_ships(board))
+    }
 
-                acc
-            },
-        );
+    fn from_candidates(board: &Board, candidates: &[&PlacementShip]) -> Heatmap {
+        let fields = Self::get_ship_heat(board, candidates)
+            .into_par_iter()
+            .fold(
+                || vec![vec![0; BOARD_SIZE as usize]; BOARD_SIZE as usize],
+                |mut acc, (ship, heat)| {
+                    for field in ship.get_occupied_fields() {
+                        acc[field.x as usize][field.y as usize] += heat;
+                    }
+
+                    acc
+                },
+            )
+            .reduce(
+                || vec![vec![0; BOARD_SIZE as usize]; BOARD_SIZE as usize],
+                |mut acc, other| {
+                    for (acc_col, other_col) in acc.iter_mut().zip(other) {
+                        for (acc_field, other_field) in acc_col.iter_mut().zip(other_col) {
+                            *acc_field += other_field;
+                        }
+                    }
+
+                    acc
+                },
+            );
 
         Heatmap { fields }
     }
+
+    /// Builds the heatmap on the compute pool, see [`spawn_compute`].
+    pub async fn compute(board: Board) -> Heatmap {
+        spawn_compute(move || Heatmap::from_board(&board)).await
+    }
+
+    /// Like [`Heatmap::compute`], but reuses the candidates of the previous
+    /// round if there is one.
+    pub async fn compute_incremental(
+        state: Option<IncrementalHeatmap>,
+        board: Board,
+    ) -> (Heatmap, IncrementalHeatmap) {
+        spawn_compute(move || match state {
+            Some(mut state) => {
+                let heatmap = state.update(&board);
+
+                (heatmap, state)
+            }
+            None => IncrementalHeatmap::new(&board),
+        })
+        .await
+    }
+}
+
+impl IncrementalHeatmap {
+    pub fn new(board: &Board) -> (Heatmap, IncrementalHeatmap) {
+        let candidates = Heatmap::get_possible_ships(board);
+        let heatmap = Heatmap::from_candidates(board, &candidates);
+
+        let state = IncrementalHeatmap {
+            board: board.clone(),
+            candidates,
+        };
+
+        (heatmap, state)
+    }
+
+    pub fn update(&mut self, board: &Board) -> Heatmap {
+        let revealed_fields = self
+            .board
+            .get_changed_fields(board)
+            .into_iter()
+            .filter(|field| board.check_field(*field, vec![FieldState::Empty]))
+            .collect_vec();
+
+        if !revealed_fields.is_empty() {
+            self.candidates = std::mem::take(&mut self.candidates)
+                .into_par_iter()
+                .filter(|ship| {
+                    !ship
+                        .ship
+                        .get_occupied_fields()
+                        .iter()
+                        .any(|field| revealed_fields.contains(field))
+                })
+                .collect();
+        }
+
+        self.board = board.clone();
+
+        let heatmap = Heatmap::from_candidates(board, &self.candidates);
+
+        if cfg!(debug_assertions) {
+            let expected = Heatmap::from_board(board);
+
+            if heatmap != expected {
+                error!("Incremental heatmap differs from full recomputation, resetting candidates");
+
+                self.candidates = Heatmap::get_possible_ships(board);
+
+                return expected;
+            }
+        }
+
+        heatmap
+    }
+
+    pub fn get_candidate_count(&self) -> usize {
+        self.candidates.len()
+    }
 }
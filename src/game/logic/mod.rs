@@ -1,9 +1,18 @@
 pub mod board;
+pub mod board_diff;
+pub mod consistency;
 pub mod direction;
+pub mod explanation;
+pub mod export;
+pub mod field_state;
+pub mod fleet;
 pub mod game_details;
 pub mod game_event;
+pub mod grid;
 pub mod heatmap;
+pub mod notation;
 pub mod placement;
 pub mod position;
+pub mod render;
 pub mod ship;
-pub mod with_bounds;
+pub mod strategy;
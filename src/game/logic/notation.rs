@@ -0,0 +1,577 @@
+//! Text notation for writing fixtures and sharing positions.
+//!
+//! - Positions use a column letter and a row number, `C7` is `x: 2, y: 6`
+//! - Ships are start, direction and size, `A1h5` is a horizontal ship of size
+//!   5 from `A1` to `E1`
+//! - Placements are ships separated by whitespace
+//! - Boards are grids of `. O x X ~` with one row per line, labels as
+//!   written by [`Board::render`] are ignored
+//! - Games list placements and moves, see [`GameRecord`]
+
+use std::{fmt, str::FromStr};
+
+use itertools::Itertools;
+
+use crate::network::models::{
+    Board, Direction, FieldState, GameDetails, Placement, Position, Ship,
+};
+
+use super::{
+    board_diff::MoveOutcome,
+    grid::{BOARD_SIZE, COLUMN_LABELS},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NotationError {
+    Position(String),
+    Direction(String),
+    Ship(String),
+    Placement(String),
+    Symbol(char),
+    BoardSize,
+    Outcome(String),
+    Line(String),
+}
+
+impl fmt::Display for NotationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NotationError::Position(value) => write!(f, "Invalid position: {}", value),
+            NotationError::Direction(value) => write!(f, "Invalid direction: {}", value),
+            NotationError::Ship(value) => write!(f, "Invalid ship: {}", value),
+            NotationError::Placement(value) => write!(f, "Invalid placement: {}", value),
+            NotationError::Symbol(value) => write!(f, "Invalid field symbol: {}", value),
+            NotationError::BoardSize => write!(
+                f,
+                "Board must have {} rows of {} fields",
+                BOARD_SIZE, BOARD_SIZE
+            ),
+            NotationError::Outcome(value) => write!(f, "Invalid outcome: {}", value),
+            NotationError::Line(value) => write!(f, "Invalid line: {}", value),
+        }
+    }
+}
+
+impl std::error::Error for NotationError {}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match COLUMN_LABELS.chars().nth(self.x as usize) {
+            Some(column) if self.x >= 0 => write!(f, "{}{}", column, self.y + 1),
+            _ => write!(f, "({}, {})", self.x, self.y),
+        }
+    }
+}
+
+impl FromStr for Position {
+    type Err = NotationError;
+
+    fn from_str(value: &str) -> Result<Position, NotationError> {
+        let error = || NotationError::Position(value.to_string());
+
+        let mut chars = value.chars();
+        let column = chars.next().ok_or_else(error)?.to_ascii_uppercase();
+        let row = chars.as_str().parse::<i8>().map_err(|_| error())?;
+
+        let x = COLUMN_LABELS.find(column).ok_or_else(error)? as i8;
+
+        if !(1..=BOARD_SIZE).contains(&row) {
+            return Err(error());
+        }
+
+        Ok(Position { x, y: row - 1 })
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Direction::Horizontal => write!(f, "h"),
+            Direction::Vertical => write!(f, "v"),
+        }
+    }
+}
+
+impl FromStr for Direction {
+    type Err = NotationError;
+
+    fn from_str(value: &str) -> Result<Direction, NotationError> {
+        match value {
+            "h" => Ok(Direction::Horizontal),
+            "v" => Ok(Direction::Vertical),
+            _ => Err(NotationError::Direction(value.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for Ship {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}{}", self.start, self.direction, self.size)
+    }
+}
+
+impl FromStr for Ship {
+    type Err = NotationError;
+
+    fn from_str(value: &str) -> Result<Ship, NotationError> {
+        let error = || NotationError::Ship(value.to_string());
+
+        // The column letter comes first, so the direction is the next letter
+        let index = value
+            .char_indices()
+            .skip(1)
+            .find(|(_, char)| char.is_ascii_alphabetic())
+            .map(|(index, _)| index)
+            .ok_or_else(error)?;
+
+        let start = value[..index].parse()?;
+        let direction = value[index..index + 1].parse()?;
+        let size = value[index + 1..].parse::<i8>().map_err(|_| error())?;
+
+        if !(1..=BOARD_SIZE).contains(&size) {
+            return Err(error());
+        }
+
+        Ok(Ship {
+            start,
+            direction,
+            size,
+        })
+    }
+}
+
+impl fmt::Display for Placement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.ships.iter().join(" "))
+    }
+}
+
+impl FromStr for Placement {
+    type Err = NotationError;
+
+    fn from_str(value: &str) -> Result<Placement, NotationError> {
+        let mut placement = Placement::new();
+
+        for ship in value.split(|char: char| char.is_whitespace() || char == ',') {
+            if ship.is_empty() {
+                continue;
+            }
+
+            placement
+                .add_ship(ship.parse()?)
+                .map_err(|err| NotationError::Placement(format!("{} ({})", ship, err)))?;
+        }
+
+        Ok(placement)
+    }
+}
+
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in 0..BOARD_SIZE {
+            let row = (0..BOARD_SIZE)
+                .map(|x| self.get_field(Position { x, y }).get_symbol())
+                .collect::<String>();
+
+            writeln!(f, "{}", row)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Board {
+    type Err = NotationError;
+
+    fn from_str(value: &str) -> Result<Board, NotationError> {
+        let mut board = Board::new();
+        let mut y = 0;
+
+        for line in value.lines() {
+            let line = line
+                .chars()
+                .filter(|char| !char.is_whitespace())
+                .collect::<String>();
+
+            // Skip the column labels and strip the row labels of rendered boards
+            let row = line.trim_start_matches(|char: char| char.is_ascii_digit());
+
+            if row.is_empty() || row == COLUMN_LABELS {
+                continue;
+            }
+
+            if y >= BOARD_SIZE || row.chars().count() != BOARD_SIZE as usize {
+                return Err(NotationError::BoardSize);
+            }
+
+            for (x, symbol) in row.chars().enumerate() {
+                let state = FieldState::from_symbol(symbol).ok_or(NotationError::Symbol(symbol))?;
+
+                board.set_field(Position { x: x as i8, y }, state);
+            }
+
+            y += 1;
+        }
+
+        if y != BOARD_SIZE {
+            return Err(NotationError::BoardSize);
+        }
+
+        Ok(board)
+    }
+}
+
+impl fmt::Display for MoveOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MoveOutcome::Miss => write!(f, "miss"),
+            MoveOutcome::Hit => write!(f, "hit"),
+            MoveOutcome::Sink => write!(f, "sink"),
+        }
+    }
+}
+
+impl FromStr for MoveOutcome {
+    type Err = NotationError;
+
+    fn from_str(value: &str) -> Result<MoveOutcome, NotationError> {
+        match value {
+            "miss" => Ok(MoveOutcome::Miss),
+            "hit" => Ok(MoveOutcome::Hit),
+            "sink" => Ok(MoveOutcome::Sink),
+            _ => Err(NotationError::Outcome(value.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordedMove {
+    /// Index of the player in [`GameDetails::players`], 0 or 1
+    pub player: usize,
+    pub position: Position,
+    pub outcome: Option<MoveOutcome>,
+}
+
+/// Placements and moves of a game, one entry per line. Players are numbered
+/// 1 and 2, outcomes are optional and lines starting with `#` are comments.
+///
+/// ```text
+/// placement 1 A1h5 A3h4 A5h3 A7h3 A9h2
+/// placement 2 J1v5 H1v4 F1v3 D1v3 B1v2
+/// move 1 C7 miss
+/// move 2 A1 hit
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GameRecord {
+    pub placements: [Option<Placement>; 2],
+    pub moves: Vec<RecordedMove>,
+}
+
+impl From<&GameDetails> for GameRecord {
+    fn from(details: &GameDetails) -> GameRecord {
+        let moves = details
+            .log
+            .iter()
+            .filter_map(|round| {
+                Some(RecordedMove {
+                    player: details
+                        .players
+                        .iter()
+                        .position(|player| player.id == round.player)?,
+                    position: round.game_move?,
+                    outcome: None,
+                })
+            })
+            .collect();
+
+        GameRecord {
+            placements: [None, None],
+            moves,
+        }
+    }
+}
+
+impl fmt::Display for GameRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, placement) in self.placements.iter().enumerate() {
+            if let Some(placement) = placement {
+                writeln!(f, "placement {} {}", index + 1, placement)?;
+            }
+        }
+
+        for game_move in &self.moves {
+            write!(f, "move {} {}", game_move.player + 1, game_move.position)?;
+
+            if let Some(outcome) = game_move.outcome {
+                write!(f, " {}", outcome)?;
+            }
+
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for GameRecord {
+    type Err = NotationError;
+
+    fn from_str(value: &str) -> Result<GameRecord, NotationError> {
+        let mut record = GameRecord::default();
+
+        for line in value.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let error = || NotationError::Line(line.to_string());
+            let mut tokens = line.split_whitespace();
+
+            let keyword = tokens.next().ok_or_else(error)?;
+            let player = match tokens.next().ok_or_else(error)? {
+                "1" => 0,
+                "2" => 1,
+                _ => return Err(error()),
+            };
+
+            match keyword {
+                "placement" => {
+                    record.placements[player] = Some(tokens.join(" ").parse()?);
+                }
+                "move" => {
+                    let position = tokens.next().ok_or_else(error)?.parse()?;
+                    let outcome = tokens.next().map(str::parse).transpose()?;
+
+                    if tokens.next().is_some() {
+                        return Err(error());
+                    }
+
+                    record.moves.push(RecordedMove {
+                        player,
+                        position,
+                        outcome,
+                    });
+                }
+                _ => return Err(error()),
+            }
+        }
+
+        Ok(record)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOARD: &str = "\
+        OOOOO.~~~~\n\
+        ..........\n\
+        xX~~~~~~~.\n\
+        ~~~~~~~~~~\n\
+        ~~~~~~~~~~\n\
+        ~~~~O~~~~~\n\
+        ~~~~O~~~~~\n\
+        ~~~~~~~~~~\n\
+        ~~~~~~~~~x\n\
+        ~~~~~~~~~X\n";
+
+    #[test]
+    fn position_round_trip() {
+        for x in 0..BOARD_SIZE {
+            for y in 0..BOARD_SIZE {
+                let position = Position { x, y };
+
+                assert_eq!(position.to_string().parse(), Ok(position));
+            }
+        }
+
+        assert_eq!("c7".parse(), Ok(Position { x: 2, y: 6 }));
+    }
+
+    #[test]
+    fn position_rejects_bad_input() {
+        for value in ["", "A", "K1", "A0", "A11", "1A", "AA1"] {
+            assert_eq!(
+                value.parse::<Position>(),
+                Err(NotationError::Position(value.to_string())),
+                "{}",
+                value
+            );
+        }
+    }
+
+    #[test]
+    fn ship_round_trip() {
+        let ship = Ship {
+            start: Position { x: 9, y: 9 },
+            direction: Direction::Vertical,
+            size: 3,
+        };
+
+        assert_eq!(ship.to_string(), "J10v3");
+        assert_eq!("J10v3".parse(), Ok(ship));
+    }
+
+    #[test]
+    fn ship_rejects_bad_input() {
+        assert_eq!(
+            "A1h-3".parse::<Ship>(),
+            Err(NotationError::Ship(String::from("A1h-3")))
+        );
+        assert_eq!(
+            "A1h0".parse::<Ship>(),
+            Err(NotationError::Ship(String::from("A1h0")))
+        );
+        assert_eq!(
+            "A1d3".parse::<Ship>(),
+            Err(NotationError::Direction(String::from("d")))
+        );
+        assert_eq!(
+            "K1h3".parse::<Ship>(),
+            Err(NotationError::Position(String::from("K1")))
+        );
+        assert_eq!(
+            "A1".parse::<Ship>(),
+            Err(NotationError::Ship(String::from("A1")))
+        );
+    }
+
+    #[test]
+    fn placement_round_trip() {
+        let placement = "A1h5 A3h4 A5h3 A7h3 A9h2".parse::<Placement>().unwrap();
+
+        assert!(placement.validate().is_empty());
+        assert_eq!(placement.to_string().parse(), Ok(placement));
+        assert_eq!(
+            "A1h5, A3h4,A5h3\nA7h3 A9h2"
+                .parse::<Placement>()
+                .unwrap()
+                .ships
+                .len(),
+            5
+        );
+    }
+
+    #[test]
+    fn placement_rejects_bad_input() {
+        assert!(matches!(
+            "A1h5 A2h4".parse::<Placement>(),
+            Err(NotationError::Placement(_))
+        ));
+        assert!(matches!(
+            "A1h5 A11h4".parse::<Placement>(),
+            Err(NotationError::Position(_))
+        ));
+    }
+
+    #[test]
+    fn board_round_trip() {
+        let board = BOARD.parse::<Board>().unwrap();
+
+        assert_eq!(
+            board.get_field(Position { x: 0, y: 2 }),
+            FieldState::Damaged
+        );
+        assert_eq!(
+            board.get_field(Position { x: 9, y: 9 }),
+            FieldState::Destroyed
+        );
+        assert_eq!(board.to_string(), BOARD);
+        assert_eq!(board.render().parse::<Board>().unwrap().to_string(), BOARD);
+    }
+
+    #[test]
+    fn board_rejects_bad_input() {
+        let nine_rows = BOARD.lines().skip(1).join("\n");
+        let short_row = BOARD.replacen("OOOOO.~~~~", "OOOOO.~~~", 1);
+        let comment = format!("# own board\n{}", BOARD);
+        let symbol = BOARD.replacen('.', "?", 1);
+
+        assert_eq!(
+            nine_rows.parse::<Board>().err(),
+            Some(NotationError::BoardSize)
+        );
+        assert_eq!(
+            short_row.parse::<Board>().err(),
+            Some(NotationError::BoardSize)
+        );
+        assert_eq!(
+            format!("{}~~~~~~~~~~\n", BOARD).parse::<Board>().err(),
+            Some(NotationError::BoardSize)
+        );
+        assert!(comment.parse::<Board>().is_err());
+        assert_eq!(
+            symbol.parse::<Board>().err(),
+            Some(NotationError::Symbol('?'))
+        );
+    }
+
+    #[test]
+    fn game_record_round_trip() {
+        let text = "\
+            # Comments and blank lines are skipped\n\
+            \n\
+            placement 1 A1h5 A3h4 A5h3 A7h3 A9h2\n\
+            placement 2 J1v5 H1v4 F1v3 D1v3 B1v2\n\
+            move 1 C7 miss\n\
+            move 2 A1 hit\n\
+            move 1 J1\n";
+
+        let record = text.parse::<GameRecord>().unwrap();
+
+        assert!(record.placements.iter().all(Option::is_some));
+        assert_eq!(
+            record.moves,
+            vec![
+                RecordedMove {
+                    player: 0,
+                    position: Position { x: 2, y: 6 },
+                    outcome: Some(MoveOutcome::Miss),
+                },
+                RecordedMove {
+                    player: 1,
+                    position: Position { x: 0, y: 0 },
+                    outcome: Some(MoveOutcome::Hit),
+                },
+                RecordedMove {
+                    player: 0,
+                    position: Position { x: 9, y: 0 },
+                    outcome: None,
+                },
+            ]
+        );
+        assert_eq!(record.to_string().parse(), Ok(record));
+    }
+
+    #[test]
+    fn game_record_rejects_bad_input() {
+        for line in [
+            "move 3 A1",
+            "move 1",
+            "move 1 A1 miss extra",
+            "shoot 1 A1",
+            "placement",
+        ] {
+            assert_eq!(
+                line.parse::<GameRecord>(),
+                Err(NotationError::Line(line.to_string())),
+                "{}",
+                line
+            );
+        }
+
+        assert_eq!(
+            "move 1 K1".parse::<GameRecord>(),
+            Err(NotationError::Position(String::from("K1")))
+        );
+        assert_eq!(
+            "move 1 A1 sunk".parse::<GameRecord>(),
+            Err(NotationError::Outcome(String::from("sunk")))
+        );
+        assert_eq!(
+            "placement 1 A1h-3".parse::<GameRecord>(),
+            Err(NotationError::Ship(String::from("A1h-3")))
+        );
+    }
+}
@@ -1,7 +1,47 @@
-use prettytable::{Cell, Row, Table};
+use std::fmt;
+
 use rand::{distributions::Standard, prelude::Distribution, Rng};
 
-use crate::network::models::{Direction, Placement, Position, Ship};
+use crate::network::models::{Placement, Position, Ship};
+
+use super::{
+    fleet::{sample_free_fleet, FLEET},
+    grid::Rect,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlacementError {
+    /// Ship doesn't fit on the board
+    OutOfBounds(Ship),
+    /// Ship overlaps or touches another ship
+    Collision { ship: Ship, other: Ship },
+    /// No ship of this size is left in the fleet
+    SizeNotInFleet(Ship),
+    /// Placement already has as many ships as the fleet
+    TooManyShips,
+    /// Ship of the fleet that wasn't placed
+    MissingShip(i8),
+}
+
+impl fmt::Display for PlacementError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlacementError::OutOfBounds(ship) => write!(f, "Ship {} is out of bounds", ship),
+            PlacementError::Collision { ship, other } => {
+                write!(f, "Ship {} collides with ship {}", ship, other)
+            }
+            PlacementError::SizeNotInFleet(ship) => {
+                write!(f, "No ship of size {} left for {}", ship.size, ship)
+            }
+            PlacementError::TooManyShips => {
+                write!(f, "Cannot add more than {} ships", FLEET.len())
+            }
+            PlacementError::MissingShip(size) => write!(f, "Ship of size {} is missing", size),
+        }
+    }
+}
+
+impl std::error::Error for PlacementError {}
 
 impl Placement {
     pub fn new() -> Placement {
@@ -9,11 +49,13 @@ impl Placement {
     }
 
     pub fn is_occupied(&self, field: &Position) -> bool {
-        return self.ships.iter().any(|ship| ship.collides_with(field));
+        self.ships
+            .iter()
+            .any(|ship| ship.collides_with(Rect::from(*field)))
     }
 
     pub fn get_missing_ship_sizes(&self) -> Vec<i8> {
-        let mut missing_ship_sizes = vec![5, 4, 3, 3, 2];
+        let mut missing_ship_sizes = FLEET.to_vec();
 
         for ship in &self.ships {
             if let Some(index) = missing_ship_sizes
@@ -27,29 +69,28 @@ impl Placement {
         missing_ship_sizes
     }
 
-    pub fn add_ship(&mut self, ship: Ship) -> Result<(), &str> {
-        if self.ships.len() >= 5 {
-            return Err("Cannot add more than 5 ships");
+    pub fn add_ship(&mut self, ship: Ship) -> Result<(), PlacementError> {
+        if self.ships.len() >= FLEET.len() {
+            return Err(PlacementError::TooManyShips);
         }
 
         if !self.get_missing_ship_sizes().contains(&ship.size) {
-            return Err("Invalid ship size");
+            return Err(PlacementError::SizeNotInFleet(ship));
         }
 
-        if self.ships.iter().any(|other| other.collides_with(&ship)) {
-            return Err("Ship collides with other ship");
+        if let Some(other) = self
+            .ships
+            .iter()
+            .find(|other| other.collides_with(ship.get_rect()))
+        {
+            return Err(PlacementError::Collision {
+                ship,
+                other: *other,
+            });
         }
 
-        if ship.start.x < 0
-            || ship.start.x > 9
-            || ship.start.y < 0
-            || ship.start.y > 9
-            || ship.get_end().x < 0
-            || ship.get_end().x > 9
-            || ship.get_end().y < 0
-            || ship.get_end().y > 9
-        {
-            return Err("Ship is out of bounds");
+        if !Rect::board().contains_rect(&ship.get_rect()) {
+            return Err(PlacementError::OutOfBounds(ship));
         }
 
         self.ships.push(ship);
@@ -58,58 +99,64 @@ impl Placement {
     }
 
     pub fn all_ships_placed(&self) -> bool {
-        self.ships.len() == 5
+        self.ships.len() == FLEET.len()
     }
 
-    pub fn print(&self) {
-        let mut board = vec![vec![" "; 10]; 10];
+    /// Checks a complete placement against the fleet rules. Unlike
+    /// `add_ship`, every violation is reported instead of only the first one.
+    pub fn validate(&self) -> Vec<PlacementError> {
+        let mut errors = Vec::new();
+        let mut missing_ship_sizes = FLEET.to_vec();
 
-        for ship in &self.ships {
-            for field in ship.get_occupied_fields() {
-                board[field.x as usize][field.y as usize] = "O";
-            }
+        if self.ships.len() > FLEET.len() {
+            errors.push(PlacementError::TooManyShips);
         }
 
-        let mut table = Table::new();
+        for (index, ship) in self.ships.iter().enumerate() {
+            if !Rect::board().contains_rect(&ship.get_rect()) {
+                errors.push(PlacementError::OutOfBounds(*ship));
+            }
 
-        for row in board {
-            table.add_row(Row::new(row.iter().map(|field| Cell::new(field)).collect()));
+            for other in &self.ships[..index] {
+                if other.collides_with(ship.get_rect()) {
+                    errors.push(PlacementError::Collision {
+                        ship: *ship,
+                        other: *other,
+                    });
+                }
+            }
+
+            match missing_ship_sizes
+                .iter()
+                .position(|size| *size == ship.size)
+            {
+                Some(index) => {
+                    missing_ship_sizes.remove(index);
+                }
+                None => errors.push(PlacementError::SizeNotInFleet(*ship)),
+            }
         }
 
-        table.printstd();
+        errors.extend(
+            missing_ship_sizes
+                .into_iter()
+                .map(PlacementError::MissingShip),
+        );
+
+        errors
     }
 }
 
 impl PartialEq for Placement {
     fn eq(&self, other: &Self) -> bool {
-        return self
-            .ships
+        self.ships
             .iter()
-            .all(|ship| other.ships.iter().any(|other_ship| ship == other_ship));
+            .all(|ship| other.ships.iter().any(|other_ship| ship == other_ship))
     }
 }
 
 impl Distribution<Placement> for Standard {
-    fn sample<R: Rng + ?Sized>(&self, _: &mut R) -> Placement {
-        let mut placement = Placement::new();
-        let mut sizes = vec![5, 4, 3, 3, 2];
-
-        while !sizes.is_empty() {
-            let size = sizes[0];
-            let start: Position = rand::random();
-            let direction: Direction = rand::random();
-
-            let ship = Ship {
-                start,
-                size,
-                direction,
-            };
-
-            if placement.add_ship(ship).is_ok() {
-                sizes.remove(0);
-            }
-        }
-
-        placement
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Placement {
+        sample_free_fleet(rng)
     }
 }
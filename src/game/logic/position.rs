@@ -3,28 +3,13 @@ use rand::Rng;
 
 use crate::network::models::Position;
 
-use super::with_bounds::WithBounds;
+use super::grid::BOARD_SIZE;
 
 impl Distribution<Position> for Standard {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Position {
         Position {
-            x: rng.gen_range(0..10),
-            y: rng.gen_range(0..10),
+            x: rng.gen_range(0..BOARD_SIZE),
+            y: rng.gen_range(0..BOARD_SIZE),
         }
     }
 }
-
-impl WithBounds for Position {
-    fn get_bounds(&self) -> (Position, Position) {
-        let start = Position {
-            x: self.x - 1,
-            y: self.y - 1,
-        };
-        let end = Position {
-            x: self.x + 1,
-            y: self.y + 1,
-        };
-
-        (start, end)
-    }
-}
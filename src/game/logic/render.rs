@@ -0,0 +1,196 @@
+use crate::network::models::{Board, FieldState, Placement, Position};
+
+use super::{
+    grid::{BOARD_SIZE, COLUMN_LABELS},
+    heatmap::Heatmap,
+};
+
+const GAP: &str = "    ";
+
+const RESET: &str = "\x1b[0m";
+const HIGHLIGHT: &str = "\x1b[1;7m";
+/// Background colors from cold to hot (ANSI 256 color palette)
+const HEAT_COLORS: [u8; 6] = [17, 19, 28, 142, 208, 196];
+
+/// Renders boards as labeled grids, columns are `x` (A-J) and rows are `y`
+/// (1-10).
+///
+/// ```text
+///    A B C D E F G H I J
+///  1 . ~ ~ ~ ~ ~ ~ ~ ~ ~
+///  2 ~ x ~ ~ ~ ~ ~ ~ ~ ~
+/// ```
+#[derive(Default)]
+pub struct BoardRenderer<'a> {
+    self_board: Option<&'a Board>,
+    opponent_board: Option<&'a Board>,
+    heatmap: Option<&'a Heatmap>,
+    shot: Option<Position>,
+    colored: bool,
+}
+
+impl<'a> BoardRenderer<'a> {
+    pub fn new() -> BoardRenderer<'a> {
+        BoardRenderer::default()
+    }
+
+    pub fn self_board(mut self, board: &'a Board) -> BoardRenderer<'a> {
+        self.self_board = Some(board);
+        self
+    }
+
+    pub fn opponent_board(mut self, board: &'a Board) -> BoardRenderer<'a> {
+        self.opponent_board = Some(board);
+        self
+    }
+
+    /// Overlays the heat on unknown fields of the opponent board, as a
+    /// background color or as a digit from 0 to 9 without colors.
+    pub fn heatmap(mut self, heatmap: &'a Heatmap) -> BoardRenderer<'a> {
+        self.heatmap = Some(heatmap);
+        self
+    }
+
+    /// Highlights a field of the opponent board.
+    pub fn shot(mut self, position: Position) -> BoardRenderer<'a> {
+        self.shot = Some(position);
+        self
+    }
+
+    /// Uses ANSI escape codes, only useful for terminals.
+    pub fn colored(mut self, colored: bool) -> BoardRenderer<'a> {
+        self.colored = colored;
+        self
+    }
+
+    pub fn render(&self) -> String {
+        let mut grids = Vec::new();
+
+        if let Some(board) = self.self_board {
+            grids.push(("Own board", self.render_grid(board, false)));
+        }
+
+        if let Some(board) = self.opponent_board {
+            grids.push(("Opponent board", self.render_grid(board, true)));
+        }
+
+        let mut lines = Vec::new();
+
+        // Titles are only needed to tell multiple boards apart
+        if grids.len() > 1 {
+            let width = 3 + 2 * BOARD_SIZE as usize;
+
+            lines.push(
+                grids
+                    .iter()
+                    .map(|(title, _)| format!("{:<width$}", title, width = width))
+                    .collect::<Vec<_>>()
+                    .join(GAP),
+            );
+        }
+
+        for row in 0..=BOARD_SIZE as usize {
+            let line = grids
+                .iter()
+                .map(|(_, grid)| grid[row].as_str())
+                .collect::<Vec<_>>()
+                .join(GAP);
+
+            lines.push(line);
+        }
+
+        lines
+            .iter()
+            .map(|line| line.trim_end())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn render_grid(&self, board: &Board, is_opponent: bool) -> Vec<String> {
+        let heatmap = self.heatmap.filter(|_| is_opponent);
+        let max_heat = heatmap
+            .and_then(|heatmap| heatmap.fields.iter().flatten().max().copied())
+            .unwrap_or(0)
+            .max(1);
+
+        let mut lines = vec![format!(
+            "   {}",
+            COLUMN_LABELS
+                .chars()
+                .map(|label| format!("{} ", label))
+                .collect::<String>()
+        )];
+
+        for y in 0..BOARD_SIZE {
+            let mut line = format!("{:>2} ", y + 1);
+
+            for x in 0..BOARD_SIZE {
+                let position = Position { x, y };
+                let state = board.get_field(position);
+                let is_shot = is_opponent && self.shot == Some(position);
+
+                let heat = heatmap
+                    .filter(|_| state == FieldState::Unknown)
+                    .map(|heatmap| heatmap.fields[x as usize][y as usize] * 9 / max_heat);
+
+                line.push_str(&self.render_field(state, heat, is_shot));
+                line.push(' ');
+            }
+
+            lines.push(line);
+        }
+
+        lines
+    }
+
+    fn render_field(&self, state: FieldState, heat: Option<u32>, is_shot: bool) -> String {
+        let symbol = state.get_symbol();
+
+        if !self.colored {
+            return match (is_shot, heat) {
+                (true, _) => String::from("*"),
+                (false, Some(heat)) => heat.to_string(),
+                (false, None) => symbol.to_string(),
+            };
+        }
+
+        if is_shot {
+            return format!("{}{}{}", HIGHLIGHT, symbol, RESET);
+        }
+
+        match heat {
+            Some(heat) => {
+                let color = HEAT_COLORS[heat as usize * (HEAT_COLORS.len() - 1) / 9];
+
+                format!("\x1b[48;5;{}m{}{}", color, symbol, RESET)
+            }
+            None => symbol.to_string(),
+        }
+    }
+}
+
+impl Board {
+    pub fn render(&self) -> String {
+        BoardRenderer::new().opponent_board(self).render()
+    }
+}
+
+impl Placement {
+    pub fn to_board(&self) -> Board {
+        let mut board = Board {
+            fields: vec![vec![FieldState::Empty; BOARD_SIZE as usize]; BOARD_SIZE as usize],
+        };
+
+        for ship in &self.ships {
+            for field in ship.get_occupied_fields() {
+                board.set_field(field, FieldState::Ship);
+            }
+        }
+
+        board
+    }
+
+    pub fn render(&self) -> String {
+        BoardRenderer::new().self_board(&self.to_board()).render()
+    }
+}
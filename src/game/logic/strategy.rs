@@ -0,0 +1,253 @@
+use std::{fmt, str::FromStr};
+
+use itertools::Itertools;
+use lazy_static::lazy_static;
+use log::debug;
+use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
+
+use crate::network::models::{Board, Direction, FieldState, Placement, Position};
+
+use super::{
+    explanation::{HuntStage, MoveExplanation, TieBreak},
+    heatmap::Heatmap,
+};
+
+lazy_static! {
+    /// Heat before the first shot, which is where heatmap players look first
+    static ref INITIAL_HEATMAP: Heatmap = Heatmap::from_board(&Board::new());
+}
+
+/// Placements drawn by `PlacementStrategy::LowHeat` to pick the coldest from
+const LOW_HEAT_SAMPLES: usize = 50;
+
+/// Ways to choose an attack. One of them plays, the others can run as
+/// shadows on the same board without their moves being sent.
+#[derive(sqlx::Type, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[sqlx(type_name = "attackstrategy", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum AttackStrategy {
+    /// Finishes damaged ships first, otherwise attacks a checkerboard
+    /// pattern. Candidates are ordered by heat.
+    HuntTarget,
+    /// Always attacks the hottest unknown field
+    MaxHeat,
+    /// Like `HuntTarget`, but takes the first candidate instead of the
+    /// hottest one
+    Parity,
+    /// Attacks a random unknown field
+    Random,
+}
+
+pub const ATTACK_STRATEGIES: [AttackStrategy; 4] = [
+    AttackStrategy::HuntTarget,
+    AttackStrategy::MaxHeat,
+    AttackStrategy::Parity,
+    AttackStrategy::Random,
+];
+
+impl AttackStrategy {
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            AttackStrategy::HuntTarget => "hunt_target",
+            AttackStrategy::MaxHeat => "max_heat",
+            AttackStrategy::Parity => "parity",
+            AttackStrategy::Random => "random",
+        }
+    }
+
+    /// Returns `None` if the strategy finds no field to attack.
+    pub fn choose(&self, board: &Board, heatmap: &Heatmap) -> Option<Position> {
+        self.explain(board, heatmap).position
+    }
+
+    /// Chooses an attack like [`AttackStrategy::choose`] and describes how.
+    pub fn explain(&self, board: &Board, heatmap: &Heatmap) -> MoveExplanation {
+        let get_heat =
+            |position: &Position| heatmap.fields[position.x as usize][position.y as usize];
+
+        let (stage, candidates) = match self {
+            AttackStrategy::HuntTarget | AttackStrategy::Parity => match hunt_target(board) {
+                Some((stage, candidates)) => (Some(stage), candidates),
+                None => (None, Vec::new()),
+            },
+            AttackStrategy::MaxHeat | AttackStrategy::Random => {
+                (None, board.find_fields(vec![FieldState::Unknown], |_| true))
+            }
+        };
+
+        let (tie_break, position) = match self {
+            AttackStrategy::HuntTarget => (
+                TieBreak::HottestFirst,
+                candidates
+                    .iter()
+                    .sorted_by(|a, b| get_heat(b).cmp(&get_heat(a)))
+                    .next()
+                    .copied(),
+            ),
+            AttackStrategy::MaxHeat => (
+                TieBreak::HottestLast,
+                candidates
+                    .iter()
+                    .max_by_key(|position| get_heat(position))
+                    .copied(),
+            ),
+            AttackStrategy::Parity => (TieBreak::FirstCandidate, candidates.first().copied()),
+            AttackStrategy::Random => (
+                TieBreak::Random,
+                candidates.choose(&mut rand::thread_rng()).copied(),
+            ),
+        };
+
+        MoveExplanation::new(*self, stage, &candidates, heatmap, tie_break, position)
+    }
+}
+
+impl fmt::Display for AttackStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.get_name())
+    }
+}
+
+impl FromStr for AttackStrategy {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<AttackStrategy, String> {
+        ATTACK_STRATEGIES
+            .into_iter()
+            .find(|strategy| strategy.get_name() == value)
+            .ok_or_else(|| format!("Unknown attack strategy: {}", value))
+    }
+}
+
+/// Ways to choose our placement on `SET`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PlacementStrategy {
+    /// Draws uniformly from all legal placements
+    Uniform,
+    /// Draws several uniform placements and keeps the one with the least heat
+    /// on an empty board
+    LowHeat,
+}
+
+pub const PLACEMENT_STRATEGIES: [PlacementStrategy; 2] =
+    [PlacementStrategy::Uniform, PlacementStrategy::LowHeat];
+
+impl PlacementStrategy {
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            PlacementStrategy::Uniform => "uniform",
+            PlacementStrategy::LowHeat => "low_heat",
+        }
+    }
+
+    pub fn choose(&self) -> Placement {
+        match self {
+            PlacementStrategy::Uniform => rand::random(),
+            PlacementStrategy::LowHeat => (0..LOW_HEAT_SAMPLES)
+                .map(|_| rand::random::<Placement>())
+                .min_by_key(|placement| {
+                    placement
+                        .ships
+                        .iter()
+                        .flat_map(|ship| ship.get_occupied_fields())
+                        .map(|field| INITIAL_HEATMAP.fields[field.x as usize][field.y as usize])
+                        .sum::<u32>()
+                })
+                .unwrap(),
+        }
+    }
+}
+
+impl fmt::Display for PlacementStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.get_name())
+    }
+}
+
+impl FromStr for PlacementStrategy {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<PlacementStrategy, String> {
+        PLACEMENT_STRATEGIES
+            .into_iter()
+            .find(|strategy| strategy.get_name() == value)
+            .ok_or_else(|| format!("Unknown placement strategy: {}", value))
+    }
+}
+
+/// Runs the hunt/target stages in order and returns the first one with
+/// candidates.
+fn hunt_target(board: &Board) -> Option<(HuntStage, Vec<Position>)> {
+    let stages = [
+        (HuntStage::Line, next_attack as fn(&Board) -> Vec<Position>),
+        (HuntStage::Neighbors, second_attack),
+        (HuntStage::Checkerboard, first_attack),
+    ];
+
+    stages.into_iter().find_map(|(stage, get_candidates)| {
+        let candidates = get_candidates(board);
+
+        if candidates.is_empty() {
+            return None;
+        }
+
+        debug!(
+            "Stage {} found {} candidates",
+            stage.get_name(),
+            candidates.len()
+        );
+
+        Some((stage, candidates))
+    })
+}
+
+fn first_attack(board: &Board) -> Vec<Position> {
+    board.find_fields(vec![FieldState::Unknown], |position| {
+        position.x % 2 == position.y % 2 && !board.is_occupied(*position)
+    })
+}
+
+fn second_attack(board: &Board) -> Vec<Position> {
+    let damaged_field = match board.find_field(vec![FieldState::Damaged], |_| true) {
+        Some(field) => field,
+        None => return Vec::new(),
+    };
+
+    damaged_field
+        .get_neighbors()
+        .filter(|&position| board.check_field(position, vec![FieldState::Unknown]))
+        .collect_vec()
+}
+
+fn next_attack(board: &Board) -> Vec<Position> {
+    let damaged_fields = board.find_fields(vec![FieldState::Damaged], |_| true);
+
+    if damaged_fields.len() == 1 {
+        return Vec::new();
+    }
+
+    let first_damaged_field = match damaged_fields.first() {
+        Some(field) => field,
+        None => return Vec::new(),
+    };
+
+    let last_damaged_field = match damaged_fields.last() {
+        Some(field) => field,
+        None => return Vec::new(),
+    };
+
+    let direction = if first_damaged_field.x != last_damaged_field.x {
+        Direction::Horizontal
+    } else {
+        Direction::Vertical
+    };
+
+    [
+        first_damaged_field.moved(direction, -1),
+        last_damaged_field.moved(direction, 1),
+    ]
+    .into_iter()
+    .filter(|&position| board.check_field(position, vec![FieldState::Unknown]))
+    .collect_vec()
+}
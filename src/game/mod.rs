@@ -1,3 +1,11 @@
+pub mod bandit;
+pub mod bot;
+pub mod consistency;
 pub mod controller;
+pub mod guard;
 pub mod handler;
 pub mod logic;
+pub mod resume;
+pub mod session;
+pub mod shadow;
+pub mod variant;
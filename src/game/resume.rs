@@ -0,0 +1,174 @@
+use std::sync::Arc;
+
+use log::{info, warn};
+use sqlx::query;
+use tokio::sync::Mutex as AsyncMutex;
+
+use crate::{
+    application::database::DB_POOL,
+    game::{
+        logic::strategy::AttackStrategy,
+        session::{get_session, insert_session, GameSession},
+        variant::{find_variant, select_variant},
+    },
+    network::models::{Board, FieldState, GameDetails},
+};
+
+/// Returns the session of the game, restoring it if the bot doesn't know the
+/// game, e.g. after a restart or a reconnect. Games that aren't stored yet
+/// are created, so any event can start a game, not only `INIT`.
+///
+/// The variant and our last attack are restored from the database and the
+/// game log, `opponent_board` lets the outcome of that attack be recorded on
+/// the next round. Our placement is read from `self_board`, the previous own
+/// board is lost.
+pub async fn get_or_resume_session(
+    details: &GameDetails,
+    opponent_board: Option<&Board>,
+    self_board: Option<&Board>,
+) -> Arc<AsyncMutex<GameSession>> {
+    if let Some(session) = get_session(&details.id) {
+        return session;
+    }
+
+    let game_id = &details.id;
+    let opponent_id = &details.get_opponent().id;
+    let stored_variant = load_game_variant(game_id).await;
+
+    let variant = match stored_variant.as_deref().map(find_variant) {
+        Some(Some(variant)) => variant,
+        Some(None) => {
+            warn!(
+                "Variant {} of game {} is no longer configured, selecting a new one",
+                stored_variant.unwrap_or_default(),
+                game_id
+            );
+            select_variant(opponent_id).await
+        }
+        None => select_variant(opponent_id).await,
+    };
+
+    let mut session = GameSession::new(game_id, variant);
+
+    store_game(game_id, opponent_id, &session.variant.name).await;
+
+    if !details.log.is_empty() {
+        info!(
+            "Resuming game {} in round {} with variant {}",
+            game_id,
+            details.log.len(),
+            session.variant.name
+        );
+
+        restore_last_attack(&mut session, details, opponent_board).await;
+    }
+
+    if let Some(self_board) = self_board {
+        restore_placement(&mut session, self_board);
+    }
+
+    insert_session(session)
+}
+
+async fn load_game_variant(game_id: &str) -> Option<String> {
+    let pool = DB_POOL.get()?;
+
+    query!("SELECT variant FROM game WHERE game_id = $1", game_id)
+        .fetch_optional(pool)
+        .await
+        .expect("Failed to load game from database")
+        .and_then(|game| game.variant)
+}
+
+/// Inserts the game if it doesn't exist yet. A replayed `INIT` keeps the
+/// stored variant and only fills in what is missing.
+async fn store_game(game_id: &str, opponent_id: &str, variant: &str) {
+    let pool = match DB_POOL.get() {
+        Some(pool) => pool,
+        None => return,
+    };
+
+    query!(
+        "INSERT INTO game (game_id, variant, opponent_id) VALUES ($1, $2, $3)
+        ON CONFLICT (game_id) DO UPDATE SET variant = COALESCE(game.variant, EXCLUDED.variant), opponent_id = COALESCE(game.opponent_id, EXCLUDED.opponent_id)",
+        game_id,
+        variant,
+        opponent_id
+    )
+    .execute(pool)
+    .await
+    .expect("Failed to insert game into database");
+}
+
+/// Takes our last attack from the log and the strategy that chose it from its
+/// stored round. The previous opponent board is the current one without that
+/// attack.
+async fn restore_last_attack(
+    session: &mut GameSession,
+    details: &GameDetails,
+    opponent_board: Option<&Board>,
+) {
+    let last_attack = details
+        .log
+        .iter()
+        .enumerate()
+        .rev()
+        .filter(|(_, round)| round.player == details.self_id)
+        .find_map(|(index, round)| Some((index, round.game_move?)));
+
+    let (round_id, last_move) = match last_attack {
+        Some(last_attack) => last_attack,
+        None => return,
+    };
+
+    session.last_move = Some(last_move);
+
+    if let Some(opponent_board) = opponent_board {
+        let mut previous_board = opponent_board.clone();
+        previous_board.set_field(last_move, FieldState::Unknown);
+
+        session.opponent_board = Some(previous_board);
+    }
+
+    let pool = match DB_POOL.get() {
+        Some(pool) => pool,
+        None => return,
+    };
+
+    session.last_strategy = query!(
+        r#"SELECT strategy as "strategy: AttackStrategy" FROM round WHERE game_id = $1 AND round_id = $2"#,
+        details.id,
+        round_id as i32
+    )
+    .fetch_optional(pool)
+    .await
+    .expect("Failed to load round from database")
+    .and_then(|round| round.strategy);
+}
+
+/// Detects our ships on our own board, which shows all of them. The placement
+/// stays unknown if the board doesn't show a legal one, it would only report
+/// desyncs that aren't there.
+fn restore_placement(session: &mut GameSession, self_board: &Board) {
+    // Hits don't matter here, but detection only starts ships on intact fields
+    let mut board = self_board.clone();
+
+    for field in board.fields.iter_mut().flatten() {
+        if *field == FieldState::Damaged {
+            *field = FieldState::Ship;
+        }
+    }
+
+    let (placement, errors) = board.detect_complete_ships();
+    let placement_errors = placement.validate();
+
+    if !errors.is_empty() || !placement_errors.is_empty() {
+        warn!(
+            "Failed to restore placement of game {}: {:?} {:?}",
+            session.game_id, errors, placement_errors
+        );
+        return;
+    }
+
+    session.placement = Some(placement);
+}
@@ -0,0 +1,151 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use lazy_static::lazy_static;
+use log::debug;
+use tokio::sync::Mutex as AsyncMutex;
+
+use crate::{
+    bot::dispatcher::IDLE_TIMEOUT,
+    game::{
+        logic::{
+            heatmap::{Heatmap, IncrementalHeatmap},
+            strategy::AttackStrategy,
+        },
+        variant::Variant,
+    },
+    network::models::{Board, Placement, Position},
+};
+
+lazy_static! {
+    static ref SESSIONS: Mutex<HashMap<String, Arc<AsyncMutex<GameSession>>>> =
+        Mutex::new(HashMap::new());
+}
+
+/// State of a running game that survives between its events.
+#[derive(Debug)]
+pub struct GameSession {
+    pub game_id: String,
+    /// Strategies this game is played with, assigned when it starts
+    pub variant: Variant,
+    /// Placement we submitted on `SET`
+    pub placement: Option<Placement>,
+    /// Boards and heatmap of the previous `ROUND`
+    pub self_board: Option<Board>,
+    pub opponent_board: Option<Board>,
+    pub heatmap: Option<Heatmap>,
+    pub heatmap_state: Option<IncrementalHeatmap>,
+    /// Last attack and the strategy that chose it, `None` for fallbacks
+    pub last_move: Option<Position>,
+    pub last_strategy: Option<AttackStrategy>,
+    /// Length of the log of the previous event, its rounds are stored
+    pub log_len: usize,
+    pub started_at: Instant,
+    pub last_event_at: Instant,
+    pub move_durations: Vec<Duration>,
+}
+
+impl GameSession {
+    pub fn new(game_id: &str, variant: Variant) -> GameSession {
+        let now = Instant::now();
+
+        GameSession {
+            game_id: game_id.to_string(),
+            variant,
+            placement: None,
+            self_board: None,
+            opponent_board: None,
+            heatmap: None,
+            heatmap_state: None,
+            last_move: None,
+            last_strategy: None,
+            log_len: 0,
+            started_at: now,
+            last_event_at: now,
+            move_durations: Vec::new(),
+        }
+    }
+
+    pub fn touch(&mut self) {
+        self.last_event_at = Instant::now();
+    }
+
+    pub fn record_move(
+        &mut self,
+        position: Position,
+        strategy: Option<AttackStrategy>,
+        duration: Duration,
+    ) {
+        self.last_move = Some(position);
+        self.last_strategy = strategy;
+        self.move_durations.push(duration);
+    }
+
+    pub fn get_average_move_duration(&self) -> Option<Duration> {
+        if self.move_durations.is_empty() {
+            return None;
+        }
+
+        let total: Duration = self.move_durations.iter().sum();
+
+        Some(total / self.move_durations.len() as u32)
+    }
+}
+
+/// Registers a new session, unless another one for the game got there first.
+pub fn insert_session(session: GameSession) -> Arc<AsyncMutex<GameSession>> {
+    SESSIONS
+        .lock()
+        .unwrap()
+        .entry(session.game_id.clone())
+        .or_insert_with(|| Arc::new(AsyncMutex::new(session)))
+        .clone()
+}
+
+pub fn get_session(game_id: &str) -> Option<Arc<AsyncMutex<GameSession>>> {
+    SESSIONS.lock().unwrap().get(game_id).cloned()
+}
+
+pub fn remove_session(game_id: &str) -> Option<Arc<AsyncMutex<GameSession>>> {
+    SESSIONS.lock().unwrap().remove(game_id)
+}
+
+/// Drops every session without events for as long as a game task stays idle
+/// and returns how many. Sessions locked by a running event are in use and
+/// kept.
+pub fn evict_stale_sessions() -> usize {
+    let mut sessions = SESSIONS.lock().unwrap();
+    let count = sessions.len();
+
+    sessions.retain(|_, session| match session.try_lock() {
+        Ok(session) => session.last_event_at.elapsed() < IDLE_TIMEOUT,
+        Err(_) => true,
+    });
+
+    count - sessions.len()
+}
+
+/// Evicts stale sessions periodically, their game was abandoned or its
+/// `RESULT` got lost after its task already stopped.
+pub fn start_session_eviction() {
+    tokio::spawn(async {
+        let mut interval = tokio::time::interval(IDLE_TIMEOUT);
+
+        loop {
+            interval.tick().await;
+
+            let evicted = evict_stale_sessions();
+
+            if evicted > 0 {
+                debug!("Evicted {} stale sessions", evicted);
+            }
+        }
+    });
+}
+
+pub fn get_session_count() -> usize {
+    SESSIONS.lock().unwrap().len()
+}
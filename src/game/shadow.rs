@@ -0,0 +1,54 @@
+use lazy_static::lazy_static;
+use log::{debug, warn};
+
+use crate::{
+    game::logic::{heatmap::Heatmap, strategy::AttackStrategy},
+    network::models::{Board, Position},
+};
+
+lazy_static! {
+    /// Strategies from `SHADOW_STRATEGIES` that choose a move every round
+    /// without playing it
+    static ref SHADOW_STRATEGIES: Vec<AttackStrategy> = std::env::var("SHADOW_STRATEGIES")
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .filter_map(|name| {
+            name.parse()
+                .map_err(|err| warn!("Ignoring shadow strategy: {}", err))
+                .ok()
+        })
+        .collect();
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ShadowMove {
+    pub strategy: AttackStrategy,
+    pub position: Position,
+    pub heat: u32,
+}
+
+/// Lets every shadow strategy except the active one choose an attack on the
+/// same board.
+pub fn get_shadow_moves(
+    active: AttackStrategy,
+    board: &Board,
+    heatmap: &Heatmap,
+) -> Vec<ShadowMove> {
+    SHADOW_STRATEGIES
+        .iter()
+        .filter(|&&strategy| strategy != active)
+        .filter_map(|&strategy| {
+            let position = strategy.choose(board, heatmap)?;
+
+            debug!("Shadow strategy {} chose {}", strategy, position);
+
+            Some(ShadowMove {
+                strategy,
+                position,
+                heat: heatmap.fields[position.x as usize][position.y as usize],
+            })
+        })
+        .collect()
+}
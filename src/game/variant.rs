@@ -0,0 +1,127 @@
+use lazy_static::lazy_static;
+use log::{debug, warn};
+use rand::seq::SliceRandom;
+
+use crate::game::logic::strategy::{AttackStrategy, PlacementStrategy};
+
+use super::bandit::{load_variant_records, sample_variant};
+
+lazy_static! {
+    /// Variants from `VARIANTS`, every game plays one of them
+    static ref VARIANTS: Vec<Variant> = parse_variants(
+        &std::env::var("VARIANTS").unwrap_or_default()
+    );
+    static ref SELECTION: VariantSelection = match std::env::var("VARIANT_SELECTION").as_deref() {
+        Ok("thompson") => VariantSelection::Thompson,
+        Ok("thompson_per_opponent") => VariantSelection::ThompsonPerOpponent,
+        Ok("weighted") | Err(_) => VariantSelection::Weighted,
+        Ok(value) => {
+            warn!("Unknown VARIANT_SELECTION {}, using weighted", value);
+            VariantSelection::Weighted
+        }
+    };
+}
+
+/// How new games get their variant
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum VariantSelection {
+    /// Randomly by the configured weights
+    Weighted,
+    /// Thompson sampling over the results of all finished games
+    Thompson,
+    /// Thompson sampling over the results against the same opponent
+    ThompsonPerOpponent,
+}
+
+/// Combination of strategies a game is played with, for comparing them on
+/// real opponents.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Variant {
+    pub name: String,
+    pub attack: AttackStrategy,
+    pub placement: PlacementStrategy,
+    /// Relative share of the games that get this variant
+    pub weight: u32,
+}
+
+impl Default for Variant {
+    fn default() -> Self {
+        Variant {
+            name: String::from("default"),
+            attack: AttackStrategy::HuntTarget,
+            placement: PlacementStrategy::Uniform,
+            weight: 1,
+        }
+    }
+}
+
+/// Picks the variant for a new game as configured by `VARIANT_SELECTION`.
+pub async fn select_variant(opponent_id: &str) -> Variant {
+    let opponent_id = match *SELECTION {
+        VariantSelection::Weighted => return choose_variant(),
+        VariantSelection::Thompson => None,
+        VariantSelection::ThompsonPerOpponent => Some(opponent_id),
+    };
+
+    let records = load_variant_records(opponent_id).await;
+
+    debug!("Sampling variant from results {:?}", records);
+
+    sample_variant(&VARIANTS, &records)
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// Picks a variant for a new game by weight.
+fn choose_variant() -> Variant {
+    VARIANTS
+        .choose_weighted(&mut rand::thread_rng(), |variant| variant.weight)
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// Looks up a configured variant by name, e.g. to resume a stored game.
+pub fn find_variant(name: &str) -> Option<Variant> {
+    if name == Variant::default().name {
+        return Some(Variant::default());
+    }
+
+    VARIANTS
+        .iter()
+        .find(|variant| variant.name == name)
+        .cloned()
+}
+
+/// Parses `name:attack:placement[:weight]` entries separated by commas,
+/// invalid entries are skipped.
+fn parse_variants(value: &str) -> Vec<Variant> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .filter_map(|entry| {
+            parse_variant(entry)
+                .map_err(|err| warn!("Ignoring variant {}: {}", entry, err))
+                .ok()
+        })
+        .collect()
+}
+
+fn parse_variant(entry: &str) -> Result<Variant, String> {
+    let parts = entry.split(':').collect::<Vec<_>>();
+
+    let (name, attack, placement, weight) = match parts.as_slice() {
+        [name, attack, placement] => (name, attack, placement, "1"),
+        [name, attack, placement, weight] => (name, attack, placement, *weight),
+        _ => return Err(String::from("Expected name:attack:placement[:weight]")),
+    };
+
+    Ok(Variant {
+        name: name.to_string(),
+        attack: attack.parse()?,
+        placement: placement.parse()?,
+        weight: weight
+            .parse()
+            .map_err(|_| format!("Invalid weight: {}", weight))?,
+    })
+}
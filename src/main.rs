@@ -1,13 +1,28 @@
 #![cfg_attr(debug_assertions, allow(dead_code))]
 use std::{thread, time::Duration};
 
-use application::{database::init_database_connection, logging::start_logger};
+use application::{
+    cli::{parse_command, Command},
+    dashboard::{is_dashboard_enabled, start_dashboard},
+    database::init_database_connection,
+    logging::start_logger,
+    thread_pool::init_thread_pool,
+};
+use bot::get_bot;
+use commands::{
+    explain_move, export_game, replay_traffic, report_shadow_strategies, report_variants,
+};
 use dotenvy::dotenv;
+use game::session::start_session_eviction;
+use log::error;
 use network::socket::init_socket_connection;
 
 mod application;
+mod bot;
+mod commands;
 mod game;
 mod network;
+mod tictactoe;
 
 pub mod utils;
 
@@ -16,8 +31,38 @@ async fn main() {
     dotenv().ok();
     let _logger_handle = start_logger();
 
-    init_database_connection().await;
-    init_socket_connection().await;
+    init_thread_pool();
+
+    let command = parse_command();
+
+    // Replays must not touch the stored games
+    if !matches!(command, Command::Replay { .. }) {
+        init_database_connection().await;
+    }
+
+    let result = match command {
+        Command::Run => run().await,
+        Command::Explain { game_id, round } => explain_move(&game_id, round).await,
+        Command::Export { game_id, directory } => export_game(&game_id, &directory).await,
+        Command::Replay { paths, variant } => replay_traffic(&paths, variant.as_deref()).await,
+        Command::ShadowReport => report_shadow_strategies().await,
+        Command::VariantReport => report_variants().await,
+    };
+
+    if let Err(err) = result {
+        error!("{:#}", err);
+        std::process::exit(1);
+    }
+}
+
+async fn run() -> anyhow::Result<()> {
+    init_socket_connection(get_bot()?).await;
+
+    start_session_eviction();
+
+    if is_dashboard_enabled() {
+        start_dashboard();
+    }
 
     loop {
         thread::sleep(Duration::from_secs(60))
@@ -1,9 +1,17 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
 use futures_util::{future::BoxFuture, FutureExt};
 use log::{debug, error, info, warn};
 use rust_socketio::{asynchronous::Client, Payload};
+use serde_json::Value;
 
 use crate::{
-    game::controller::handle_game_event, network::handler::authenticate,
+    bot::{dispatcher::dispatch, GameBot},
+    network::{
+        handler::authenticate,
+        recorder::{record_incoming, record_outgoing},
+    },
     utils::payload::deserialize_payload,
 };
 
@@ -30,40 +38,69 @@ pub fn handle_error(payload: Payload, _: Client, _: Option<i32>) -> BoxFuture<'s
     .boxed()
 }
 
+/// Hands the event to the dispatcher and acks its response once it is ready,
+/// without blocking the socket for events of other games.
 pub fn handle_data(
+    bot: Arc<dyn GameBot>,
     payload: Payload,
     client: Client,
     packet_id: Option<i32>,
 ) -> BoxFuture<'static, ()> {
     async move {
-        match deserialize_payload(&payload) {
-            Ok(event) => {
-                if let Some(packet_id) = packet_id {
-                    debug!("Received event ({:#?}): {:#?}", packet_id, event);
-                } else {
-                    debug!("Received event: {:#?}", event);
-                }
-
-                let response = handle_game_event(event).await;
-
-                if let Some(response) = response {
-                    debug!(
-                        "Sending response ({:#?}): {:#?}",
-                        packet_id.expect("Packet ID not found"),
-                        response
-                    );
-
-                    client
-                        .emit_ack(packet_id, serde_json::to_string(&response).unwrap())
-                        .await
-                        .unwrap_or_else(|err| error!("Server unreachable: {}", err));
-                }
-            }
+        record_incoming(&payload, packet_id);
+
+        let event = match deserialize_payload::<Value>(&payload) {
+            Ok(event) => event,
             Err(err) => {
-                error!("Invalid payload: {:#?}", payload);
-                error!("{:#?}", err);
+                error!("Invalid {} payload: {:#?}", bot.get_name(), payload);
+                error!("{}", err);
+                return;
             }
-        }
+        };
+
+        let response = dispatch(bot.clone(), event);
+
+        tokio::spawn(async move {
+            let response = match response.await {
+                Ok(response) => response,
+                Err(err) => Err(anyhow!(err)),
+            };
+
+            send_response(bot.as_ref(), payload, client, packet_id, response).await;
+        });
     }
     .boxed()
 }
+
+async fn send_response(
+    bot: &dyn GameBot,
+    payload: Payload,
+    client: Client,
+    packet_id: Option<i32>,
+    response: anyhow::Result<Option<Value>>,
+) {
+    match response {
+        Ok(Some(response)) => {
+            let packet_id = match packet_id {
+                Some(packet_id) => packet_id,
+                None => return,
+            };
+
+            debug!("Sending response ({:#?}): {:#?}", packet_id, response);
+
+            let data = serde_json::to_string(&response).unwrap();
+
+            record_outgoing(&data, Some(packet_id));
+
+            client
+                .emit_ack(Some(packet_id), data)
+                .await
+                .unwrap_or_else(|err| error!("Server unreachable: {}", err));
+        }
+        Ok(None) => {}
+        Err(err) => {
+            error!("Invalid {} payload: {:#?}", bot.get_name(), payload);
+            error!("{:#?}", err);
+        }
+    }
+}
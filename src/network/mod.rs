@@ -2,4 +2,5 @@ mod controller;
 mod handler;
 
 pub mod models;
+pub mod recorder;
 pub mod socket;
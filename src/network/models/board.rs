@@ -1,8 +1,8 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use super::FieldState;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Board {
     pub fields: Vec<Vec<FieldState>>,
 }
@@ -23,3 +23,12 @@ impl<'de> Deserialize<'de> for Board {
         Ok(Board { fields })
     }
 }
+
+impl Serialize for Board {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.fields.serialize(serializer)
+    }
+}
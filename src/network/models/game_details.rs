@@ -1,12 +1,18 @@
 use serde::Deserialize;
+use serde_json::{Map, Value};
 
 use super::{GameLog, Player};
 
 #[derive(Deserialize, Debug)]
 pub struct GameDetails {
     pub id: String,
+    #[serde(default)]
     pub log: Vec<GameLog>,
     pub players: [Player; 2],
     #[serde(rename = "self")]
     pub self_id: String,
+    /// Keys of the event that neither the details nor its event type know,
+    /// since the details are flattened into every event
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
@@ -1,18 +1,16 @@
-use serde::Deserialize;
+use serde::{de::Error, Deserialize};
+use serde_json::Value;
 
 use super::{Board, BoardOrBool, GameDetails};
 
-#[derive(Deserialize, Debug)]
-#[serde(tag = "type")]
+#[derive(Debug)]
 pub enum GameEvent {
-    #[serde(rename = "INIT")]
     Init(GameInitEvent),
-    #[serde(rename = "RESULT")]
     Result(GameResultEvent),
-    #[serde(rename = "ROUND")]
     Round(GameRoundEvent),
-    #[serde(rename = "SET")]
     Set(GameSetEvent),
+    /// Event type we don't know (yet)
+    Unknown(UnknownGameEvent),
 }
 
 #[derive(Deserialize, Debug)]
@@ -40,3 +38,46 @@ pub struct GameSetEvent {
     #[serde(flatten)]
     pub details: GameDetails,
 }
+
+#[derive(Debug)]
+pub struct UnknownGameEvent {
+    pub event_type: String,
+    /// Only set if the event has the usual game details
+    pub details: Option<GameDetails>,
+    pub payload: Value,
+}
+
+impl<'de> Deserialize<'de> for GameEvent {
+    fn deserialize<D>(deserializer: D) -> Result<GameEvent, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let payload = Value::deserialize(deserializer)?;
+
+        let event_type = payload
+            .get("type")
+            .and_then(Value::as_str)
+            .ok_or_else(|| D::Error::missing_field("type"))?
+            .to_string();
+
+        let mut fields = payload.clone();
+
+        if let Some(fields) = fields.as_object_mut() {
+            fields.remove("type");
+        }
+
+        let event = match event_type.as_str() {
+            "INIT" => serde_json::from_value(fields).map(GameEvent::Init),
+            "RESULT" => serde_json::from_value(fields).map(GameEvent::Result),
+            "ROUND" => serde_json::from_value(fields).map(GameEvent::Round),
+            "SET" => serde_json::from_value(fields).map(GameEvent::Set),
+            _ => Ok(GameEvent::Unknown(UnknownGameEvent {
+                details: serde_json::from_value(fields).ok(),
+                event_type,
+                payload,
+            })),
+        };
+
+        event.map_err(D::Error::custom)
+    }
+}
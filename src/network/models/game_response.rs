@@ -1,4 +1,5 @@
 use serde::Serialize;
+use serde_json::Value;
 
 use crate::network::models::{Placement, Position};
 
@@ -7,4 +8,6 @@ use crate::network::models::{Placement, Position};
 pub enum GameResponse {
     PlaceShips(Placement),
     Attack(Position),
+    /// Sent as is, e.g. to acknowledge unknown events
+    Raw(Value),
 }
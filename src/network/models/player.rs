@@ -1,10 +1,17 @@
 use serde::Deserialize;
+use serde_json::{Map, Value};
 
 use super::PlayerSymbol;
 
 #[derive(Deserialize, Debug)]
 pub struct Player {
     pub id: String,
+    #[serde(default)]
     pub score: u32,
+    #[serde(default)]
     pub symbol: Option<PlayerSymbol>,
+    /// Keys of a player entry besides `id`, `score` and `symbol`, only shown
+    /// in the debug log of the event
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
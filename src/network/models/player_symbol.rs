@@ -1,7 +1,9 @@
 use serde::Deserialize;
 
-#[derive(Deserialize, Debug, PartialEq)]
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
 pub enum PlayerSymbol {
     O,
     X,
+    #[serde(other)]
+    Unknown,
 }
@@ -0,0 +1,135 @@
+use std::{
+    fs::{self, File, OpenOptions},
+    io::Write,
+    path::PathBuf,
+    sync::Mutex,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use lazy_static::lazy_static;
+use log::{error, info};
+use rust_socketio::Payload;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+lazy_static! {
+    static ref RECORDER: Option<Mutex<Recorder>> = std::env::var("RECORDING_DIR")
+        .ok()
+        .filter(|directory| !directory.is_empty())
+        .map(|directory| Mutex::new(Recorder::new(PathBuf::from(directory))));
+}
+
+/// Files are rotated once they exceed this size, like the log files
+const DEFAULT_MAX_FILE_SIZE: u64 = 1024 * 1024 * 25;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TrafficDirection {
+    Incoming,
+    Outgoing,
+}
+
+/// One line of a recording file
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TrafficRecord {
+    /// Milliseconds since the unix epoch
+    pub timestamp: u64,
+    pub direction: TrafficDirection,
+    pub packet_id: Option<i32>,
+    pub payload: Value,
+}
+
+struct Recorder {
+    directory: PathBuf,
+    max_file_size: u64,
+    file: Option<File>,
+    file_size: u64,
+}
+
+impl Recorder {
+    fn new(directory: PathBuf) -> Recorder {
+        let max_file_size = std::env::var("RECORDING_MAX_SIZE")
+            .ok()
+            .and_then(|size| size.parse().ok())
+            .unwrap_or(DEFAULT_MAX_FILE_SIZE);
+
+        info!("Recording traffic to {}", directory.display());
+
+        Recorder {
+            directory,
+            max_file_size,
+            file: None,
+            file_size: 0,
+        }
+    }
+
+    fn write(&mut self, record: &TrafficRecord) -> std::io::Result<()> {
+        let mut line = serde_json::to_vec(record)?;
+        line.push(b'\n');
+
+        if self.file.is_none() || self.file_size + line.len() as u64 > self.max_file_size {
+            self.rotate(record.timestamp)?;
+        }
+
+        let file = self.file.as_mut().unwrap();
+        file.write_all(&line)?;
+        file.flush()?;
+
+        self.file_size += line.len() as u64;
+
+        Ok(())
+    }
+
+    fn rotate(&mut self, timestamp: u64) -> std::io::Result<()> {
+        fs::create_dir_all(&self.directory)?;
+
+        let path = self.directory.join(format!("traffic_{}.ndjson", timestamp));
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+
+        self.file_size = file.metadata()?.len();
+        self.file = Some(file);
+
+        Ok(())
+    }
+}
+
+pub fn record_incoming(payload: &Payload, packet_id: Option<i32>) {
+    let payload = match payload {
+        Payload::String(data) => parse_json(data),
+        Payload::Binary(data) => Value::from(data.to_vec()),
+    };
+
+    record(TrafficDirection::Incoming, packet_id, payload);
+}
+
+pub fn record_outgoing(data: &str, packet_id: Option<i32>) {
+    record(TrafficDirection::Outgoing, packet_id, parse_json(data));
+}
+
+fn record(direction: TrafficDirection, packet_id: Option<i32>, payload: Value) {
+    let recorder = match RECORDER.as_ref() {
+        Some(recorder) => recorder,
+        None => return,
+    };
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or(0);
+
+    let record = TrafficRecord {
+        timestamp,
+        direction,
+        packet_id,
+        payload,
+    };
+
+    if let Err(err) = recorder.lock().unwrap().write(&record) {
+        error!("Failed to record traffic: {}", err);
+    }
+}
+
+/// Keeps payloads that aren't valid JSON as plain strings
+fn parse_json(data: &str) -> Value {
+    serde_json::from_str(data).unwrap_or_else(|_| Value::String(data.to_string()))
+}
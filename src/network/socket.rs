@@ -1,3 +1,6 @@
+use std::sync::Arc;
+
+use log::info;
 use rust_socketio::asynchronous::ClientBuilder;
 use rust_socketio::Event;
 use rust_socketio::TransportType;
@@ -6,16 +9,22 @@ use super::controller::handle_close;
 use super::controller::handle_connect;
 use super::controller::handle_data;
 use super::controller::handle_error;
+use crate::bot::GameBot;
 
-pub async fn init_socket_connection() {
+pub async fn init_socket_connection(bot: Arc<dyn GameBot>) {
     let gameserver = std::env::var("GAMESERVER").expect("GAMESERVER not set");
 
+    info!("Playing {}", bot.get_name());
+
     let _socket = ClientBuilder::new(gameserver)
         .transport_type(TransportType::Websocket)
         .on(Event::Error, handle_error)
         .on(Event::Connect, handle_connect)
         .on(Event::Close, handle_close)
-        .on(Event::Custom(String::from("data")), handle_data)
+        .on(
+            Event::Custom(String::from("data")),
+            move |payload, client, packet_id| handle_data(bot.clone(), payload, client, packet_id),
+        )
         .connect()
         .await
         .expect("Connection failed");
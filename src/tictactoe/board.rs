@@ -0,0 +1,66 @@
+use crate::network::models::Position;
+
+use super::models::{TicTacToeBoard, TicTacToeField};
+
+const LINES: [[(usize, usize); 3]; 8] = [
+    [(0, 0), (1, 0), (2, 0)],
+    [(0, 1), (1, 1), (2, 1)],
+    [(0, 2), (1, 2), (2, 2)],
+    [(0, 0), (0, 1), (0, 2)],
+    [(1, 0), (1, 1), (1, 2)],
+    [(2, 0), (2, 1), (2, 2)],
+    [(0, 0), (1, 1), (2, 2)],
+    [(2, 0), (1, 1), (0, 2)],
+];
+
+impl TicTacToeField {
+    pub fn get_opponent(&self) -> TicTacToeField {
+        match self {
+            TicTacToeField::X => TicTacToeField::O,
+            TicTacToeField::O => TicTacToeField::X,
+            TicTacToeField::Empty => TicTacToeField::Empty,
+        }
+    }
+}
+
+impl TicTacToeBoard {
+    pub fn get_field(&self, position: Position) -> TicTacToeField {
+        self.0[position.x as usize][position.y as usize]
+    }
+
+    pub fn set_field(&mut self, position: Position, field: TicTacToeField) {
+        self.0[position.x as usize][position.y as usize] = field;
+    }
+
+    pub fn get_empty_fields(&self) -> Vec<Position> {
+        (0..3)
+            .flat_map(|x| (0..3).map(move |y| Position { x, y }))
+            .filter(|&position| self.get_field(position) == TicTacToeField::Empty)
+            .collect()
+    }
+
+    pub fn get_winner(&self) -> Option<TicTacToeField> {
+        LINES.iter().find_map(|line| {
+            let [a, b, c] = line.map(|(x, y)| self.0[x][y]);
+
+            (a != TicTacToeField::Empty && a == b && b == c).then_some(a)
+        })
+    }
+
+    /// The symbol whose turn it is, X always starts
+    pub fn get_next_symbol(&self) -> TicTacToeField {
+        let count = |symbol| {
+            self.0
+                .iter()
+                .flatten()
+                .filter(|&&field| field == symbol)
+                .count()
+        };
+
+        if count(TicTacToeField::X) > count(TicTacToeField::O) {
+            TicTacToeField::O
+        } else {
+            TicTacToeField::X
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use anyhow::anyhow;
+use futures_util::{future::BoxFuture, FutureExt};
+use log::{debug, info, warn};
+use serde_json::Value;
+
+use crate::{application::thread_pool::spawn_compute, bot::GameBot};
+
+use super::{
+    minimax::find_best_move,
+    models::{TicTacToeEvent, TicTacToeField},
+};
+
+pub struct TicTacToeBot;
+
+impl GameBot for TicTacToeBot {
+    fn get_name(&self) -> &'static str {
+        "tictactoe"
+    }
+
+    fn handle_event(&self, event: Value) -> BoxFuture<'static, anyhow::Result<Option<Value>>> {
+        async move {
+            let event = serde_json::from_value::<TicTacToeEvent>(event)?;
+
+            debug!("Received event: {:#?}", event);
+
+            match event {
+                TicTacToeEvent::Init(event) => {
+                    info!("Started tic-tac-toe game {}!", event.details.id);
+                    Ok(None)
+                }
+                TicTacToeEvent::Round(event) => {
+                    let board = event.board;
+
+                    // Fall back to the turn order if the server didn't send our symbol
+                    let symbol = event
+                        .details
+                        .get_self()
+                        .symbol
+                        .map(TicTacToeField::from)
+                        .filter(|&symbol| symbol != TicTacToeField::Empty)
+                        .unwrap_or_else(|| board.get_next_symbol());
+
+                    let position = spawn_compute(move || find_best_move(&board, symbol))
+                        .await
+                        .ok_or_else(|| anyhow!("No empty field in game {}", event.details.id))?;
+
+                    debug!(
+                        "Placing {:?} at {:?} in game {}",
+                        symbol, position, event.details.id
+                    );
+
+                    Ok(Some(serde_json::to_value(position)?))
+                }
+                TicTacToeEvent::Result(event) => {
+                    let self_player = event.details.get_self();
+                    let opponent = event.details.get_opponent();
+
+                    info!(
+                        "Finished tic-tac-toe game {} with {}:{}",
+                        event.details.id, self_player.score, opponent.score
+                    );
+                    Ok(None)
+                }
+                TicTacToeEvent::Unknown => {
+                    warn!("Received unknown tic-tac-toe event");
+                    Ok(None)
+                }
+            }
+        }
+        .boxed()
+    }
+
+    fn get_fallback_response(&self, event: &Value) -> Option<Value> {
+        match serde_json::from_value::<TicTacToeEvent>(event.clone()).ok()? {
+            TicTacToeEvent::Round(event) => event
+                .board
+                .get_empty_fields()
+                .first()
+                .map(|position| serde_json::to_value(position).unwrap()),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use crate::network::models::Position;
+
+use super::models::{TicTacToeBoard, TicTacToeField};
+
+/// Finds the best move for `symbol` by searching the whole game tree, which
+/// is small enough for tic-tac-toe. Faster wins and slower losses are
+/// preferred.
+pub fn find_best_move(board: &TicTacToeBoard, symbol: TicTacToeField) -> Option<Position> {
+    let mut board = *board;
+
+    board
+        .get_empty_fields()
+        .into_iter()
+        .max_by_key(|&position| {
+            board.set_field(position, symbol);
+            let score = -minimax(&mut board, symbol.get_opponent(), 1);
+            board.set_field(position, TicTacToeField::Empty);
+
+            score
+        })
+}
+
+/// Scores the board from the perspective of the player to move.
+fn minimax(board: &mut TicTacToeBoard, symbol: TicTacToeField, depth: i32) -> i32 {
+    if board.get_winner().is_some() {
+        // The previous move won the game
+        return depth - 10;
+    }
+
+    let fields = board.get_empty_fields();
+
+    if fields.is_empty() {
+        return 0;
+    }
+
+    fields
+        .into_iter()
+        .map(|position| {
+            board.set_field(position, symbol);
+            let score = -minimax(board, symbol.get_opponent(), depth + 1);
+            board.set_field(position, TicTacToeField::Empty);
+
+            score
+        })
+        .max()
+        .unwrap_or(0)
+}
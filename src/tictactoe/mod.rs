@@ -0,0 +1,4 @@
+pub mod board;
+pub mod bot;
+pub mod minimax;
+pub mod models;
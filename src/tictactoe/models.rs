@@ -0,0 +1,53 @@
+use serde::Deserialize;
+
+use crate::network::models::{GameDetails, PlayerSymbol};
+
+#[derive(Deserialize, Debug)]
+#[serde(tag = "type")]
+pub enum TicTacToeEvent {
+    #[serde(rename = "INIT")]
+    Init(TicTacToeGameEvent),
+    #[serde(rename = "ROUND")]
+    Round(TicTacToeRoundEvent),
+    #[serde(rename = "RESULT")]
+    Result(TicTacToeGameEvent),
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct TicTacToeGameEvent {
+    #[serde(flatten)]
+    pub details: GameDetails,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct TicTacToeRoundEvent {
+    #[serde(flatten)]
+    pub details: GameDetails,
+    pub board: TicTacToeBoard,
+}
+
+/// Fields are indexed by `[x][y]` like the battleship boards, moves are sent
+/// back as `[x, y]`
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TicTacToeBoard(pub [[TicTacToeField; 3]; 3]);
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TicTacToeField {
+    X,
+    O,
+    /// Sent as an empty string
+    #[serde(other)]
+    Empty,
+}
+
+impl From<PlayerSymbol> for TicTacToeField {
+    fn from(symbol: PlayerSymbol) -> Self {
+        match symbol {
+            PlayerSymbol::X => TicTacToeField::X,
+            PlayerSymbol::O => TicTacToeField::O,
+            PlayerSymbol::Unknown => TicTacToeField::Empty,
+        }
+    }
+}